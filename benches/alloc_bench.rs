@@ -0,0 +1,22 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use heapbuf::HBuf;
+
+const SIZE: usize = 256 * 1024 * 1024;
+
+fn alloc_bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("allocate_256mib");
+    group.sample_size(10);
+
+    group.bench_function("allocate_and_fill", |b| b.iter(|| {
+        let mut buf = HBuf::allocate(black_box(SIZE));
+        buf.fill(0);
+        buf
+    }));
+
+    group.bench_function("allocate_zeroed", |b| b.iter(|| HBuf::allocate_zeroed(black_box(SIZE))));
+
+    group.finish();
+}
+
+criterion_group!(benches, alloc_bench);
+criterion_main!(benches);
@@ -0,0 +1,27 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use heapbuf::HBuf;
+
+const SIZE: usize = 64 * 1024 * 1024;
+
+fn fill_bench(c: &mut Criterion) {
+    let mut buf = HBuf::allocate(SIZE);
+    let mut group = c.benchmark_group("fill_64mib");
+    group.throughput(Throughput::Bytes(SIZE as u64));
+    group.sample_size(20);
+
+    group.bench_function("byte_loop", |b| b.iter(|| {
+        let slice = buf.as_mut_slice();
+        for x in 0..slice.len() {
+            slice[x] = black_box(0xAA);
+        }
+    }));
+
+    group.bench_function("slice_fill", |b| b.iter(|| buf.as_mut_slice().fill(black_box(0xAA))));
+
+    group.bench_function("hbuf_fill", |b| b.iter(|| buf.fill(black_box(0xAA))));
+
+    group.finish();
+}
+
+criterion_group!(benches, fill_bench);
+criterion_main!(benches);
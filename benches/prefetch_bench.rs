@@ -0,0 +1,34 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use heapbuf::HBuf;
+
+const SIZE: usize = 256 * 1024 * 1024;
+const STRIDE: usize = 4096 + 64;
+const DISTANCE: usize = 8;
+
+fn walk(buf: &HBuf, prefetch: bool) -> u64 {
+    let mut sum = 0u64;
+    let mut index = 0;
+    while index < buf.limit() {
+        if prefetch {
+            buf.prefetch_read(index + STRIDE * DISTANCE);
+        }
+        sum = sum.wrapping_add(buf[index] as u64);
+        index += STRIDE;
+    }
+    sum
+}
+
+fn prefetch_bench(c: &mut Criterion) {
+    let mut buf = HBuf::allocate(SIZE);
+    buf.fill_with(|index| index as u8);
+
+    let mut group = c.benchmark_group("strided_walk_256mib");
+    group.throughput(Throughput::Elements((SIZE / STRIDE) as u64));
+    group.sample_size(20);
+    group.bench_function("without_prefetch", |b| b.iter(|| walk(black_box(&buf), false)));
+    group.bench_function("with_prefetch", |b| b.iter(|| walk(black_box(&buf), true)));
+    group.finish();
+}
+
+criterion_group!(benches, prefetch_bench);
+criterion_main!(benches);
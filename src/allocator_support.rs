@@ -0,0 +1,47 @@
+use std::alloc::{Allocator, Layout};
+use std::fmt::{Debug, Formatter};
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::ptr::NonNull;
+use crate::{DynDestructor, HBuf, HBufError};
+
+struct AllocatorDestructor<A: Allocator> {
+    allocator: A,
+    layout: Layout
+}
+
+impl<A: Allocator> Debug for AllocatorDestructor<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AllocatorDestructor").field("layout", &self.layout).finish()
+    }
+}
+
+impl<A: Allocator+Send+Sync+RefUnwindSafe+UnwindSafe> DynDestructor for AllocatorDestructor<A> {
+    fn destroy(&mut self, ptr: *mut u8, _size: usize) {
+        unsafe { self.allocator.deallocate(NonNull::new_unchecked(ptr), self.layout) }
+    }
+}
+
+impl HBuf {
+
+    ///
+    /// Allocates memory with the given size and alignment using the given allocator.
+    /// The allocator is moved into the destructor of the HBuf and used to deallocate the memory
+    /// once no more references to the HBuf exist.
+    ///
+    /// The memory of a HBuf allocated by this function can never be resized.
+    ///
+    pub fn allocate_in<A: Allocator+Send+Sync+RefUnwindSafe+UnwindSafe+'static>(size: usize, alignment: usize, allocator: A) -> Result<HBuf, HBufError> {
+        if size == 0 {
+            return Err(HBufError::ZeroSize);
+        }
+
+        let layout = Layout::from_size_align(size, alignment)?;
+        let data = allocator.allocate(layout).map_err(|_| HBufError::OutOfMemory)?;
+        let destructor = AllocatorDestructor {
+            allocator,
+            layout
+        };
+
+        Ok(unsafe { HBuf::from_raw_parts_with_dyn_destructor(data.as_ptr().cast(), size, Box::new(destructor)) })
+    }
+}
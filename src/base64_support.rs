@@ -0,0 +1,61 @@
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use crate::{HBuf, HBufError};
+
+const CONFIG: GeneralPurposeConfig = GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+const STANDARD: GeneralPurpose = GeneralPurpose::new(&alphabet::STANDARD, CONFIG);
+const URL_SAFE: GeneralPurpose = GeneralPurpose::new(&alphabet::URL_SAFE, CONFIG);
+
+///
+/// The alphabet that is used to encode and decode base64.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Base64Alphabet {
+    ///
+    /// The standard alphabet from RFC 4648 that uses '+' and '/'.
+    ///
+    Standard,
+    ///
+    /// The url and filename safe alphabet from RFC 4648 that uses '-' and '_'.
+    ///
+    UrlSafe
+}
+
+impl Base64Alphabet {
+    fn engine(self) -> &'static GeneralPurpose {
+        match self {
+            Base64Alphabet::Standard => &STANDARD,
+            Base64Alphabet::UrlSafe => &URL_SAFE
+        }
+    }
+}
+
+impl HBuf {
+
+    ///
+    /// Encodes all bytes up to the limit as base64 using the given alphabet.
+    /// The output is padded with '='.
+    ///
+    pub fn to_base64(&self, alphabet: Base64Alphabet) -> String {
+        alphabet.engine().encode(self.as_slice())
+    }
+
+    ///
+    /// Allocates a HBuf that contains the bytes encoded by the base64 string.
+    /// The padding is optional.
+    ///
+    /// Fails with HBufError::InvalidBase64 if the string is not valid base64 in the given alphabet
+    /// and with HBufError::ZeroSize if the string encodes no bytes.
+    ///
+    pub fn from_base64(encoded: &str, alphabet: Base64Alphabet) -> Result<HBuf, HBufError> {
+        let decoded = alphabet.engine().decode(encoded).map_err(|_| HBufError::InvalidBase64)?;
+        if decoded.is_empty() {
+            return Err(HBufError::ZeroSize);
+        }
+
+        let mut buf = HBuf::try_allocate(decoded.len())?;
+        buf.copy_from_slice(0, &decoded);
+        Ok(buf)
+    }
+}
@@ -1,20 +1,31 @@
 use std::alloc::{Layout, LayoutError};
-use std::fmt::{Binary, Debug, Display, Formatter, LowerHex, UpperHex};
+use std::borrow::{Borrow, BorrowMut};
+use std::fmt::{Alignment, Binary, Debug, Display, Formatter, LowerHex, UpperHex, Write as FmtWrite};
 use std::hash::{Hash, Hasher};
 use std::io;
-use std::io::{Error, ErrorKind, Read, Seek, SeekFrom, Write};
-use std::mem::{align_of, size_of};
-use std::ops::{Deref, DerefMut, Index, IndexMut};
+use std::io::{BufRead, Error, ErrorKind, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use std::iter::FusedIterator;
+use std::mem::{align_of, size_of, ManuallyDrop};
+use std::ops::{Bound, Deref, DerefMut, Index, IndexMut, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::ptr::NonNull;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
 use sync_ptr::{FromMutPtr, SyncMutPtr};
-use crate::destructor::{HBufDestructor, HBufDestructorInfo};
+use crate::destructor::{secure_zero, HBufDestructor, HBufDestructorInfo};
 
 pub enum HBufError {
     ZeroSize,
     OutOfMemory,
-    LayoutError
+    LayoutError,
+    Shared,
+    NotOwned,
+    InvalidHex,
+    InvalidBase64,
+    OutOfBounds {
+        index: usize,
+        limit: usize
+    }
 }
 
 impl From<LayoutError> for HBufError {
@@ -29,6 +40,11 @@ impl From<HBufError> for std::io::Error {
             HBufError::ZeroSize => Error::new(ErrorKind::Other, "Cannot allocate zero sized buffer"),
             HBufError::OutOfMemory =>  Error::new(ErrorKind::OutOfMemory, "OutOfMemory"),
             HBufError::LayoutError => Error::new(ErrorKind::Other, "Invalid Memory Layout"),
+            HBufError::Shared => Error::new(ErrorKind::Other, "HBuf is shared with other references"),
+            HBufError::NotOwned => Error::new(ErrorKind::Other, "HBuf does not own memory allocated by the rust allocator"),
+            HBufError::InvalidHex => Error::new(ErrorKind::InvalidData, "String is not an even number of hex digits"),
+            HBufError::InvalidBase64 => Error::new(ErrorKind::InvalidData, "String is not valid base64"),
+            HBufError::OutOfBounds { index, limit } => Error::new(ErrorKind::UnexpectedEof, format!("Index {} is out of bounds for HBuf with limit {}", index, limit)),
         }
     }
 }
@@ -44,7 +60,12 @@ impl Debug for HBufError {
         match self {
             HBufError::ZeroSize => write!(f, "HBufError::ZeroSize"),
             HBufError::OutOfMemory => write!(f, "HBufError::OutOfMemory"),
-            HBufError::LayoutError => write!(f, "HBufError::LayoutError")
+            HBufError::LayoutError => write!(f, "HBufError::LayoutError"),
+            HBufError::Shared => write!(f, "HBufError::Shared"),
+            HBufError::NotOwned => write!(f, "HBufError::NotOwned"),
+            HBufError::InvalidHex => write!(f, "HBufError::InvalidHex"),
+            HBufError::InvalidBase64 => write!(f, "HBufError::InvalidBase64"),
+            HBufError::OutOfBounds { index, limit } => write!(f, "HBufError::OutOfBounds {{ index: {}, limit: {} }}", index, limit)
         }
     }
 }
@@ -55,11 +76,44 @@ pub struct HBuf {
     capacity: usize,
     limit: usize,
     position: usize,
-    destructor: Arc<Option<HBufDestructor>>
+    mark: Option<usize>,
+    destructor: Arc<Option<HBufDestructor>>,
+    shared_limit: Option<Arc<AtomicUsize>>
 }
+///
+/// Hashes the same way as the slice returned by as_slice so that HBuf can be looked up by a [u8] key.
+///
 impl Hash for HBuf {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write(self.as_slice());
+        self.as_slice().hash(state);
+    }
+}
+
+///
+/// Compares the bytes up to the limit.
+/// This comparison is not constant time, use ct_eq for that.
+///
+impl PartialEq for HBuf {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for HBuf {}
+
+///
+/// Compares the bytes up to the limit lexicographically, like comparing the slices returned by as_slice.
+/// Bytes between limit and capacity are not considered.
+///
+impl PartialOrd for HBuf {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HBuf {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
     }
 }
 
@@ -71,7 +125,7 @@ impl Binary for HBuf {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         unsafe {
             for x in 0..self.capacity {
-                write!(f, "{:08o}", *self.data_ptr.add(x))?;
+                write!(f, "{:08b}", *self.data_ptr.add(x))?;
             }
         }
 
@@ -81,38 +135,107 @@ impl Binary for HBuf {
 
 ///
 /// This implementation does not strip leading 0s.
-/// Length of the format result will always be capacity*2
+/// Only the bytes up to the limit are formatted, so the length of the format result is limit*2.
+/// If a precision is given then at most that many bytes are formatted, "{:.8x}" formats the first 8 bytes.
+/// If a width is given then the result is padded with the fill character according to the alignment.
 ///
 impl LowerHex for HBuf {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        unsafe {
-            for x in 0..self.capacity {
-                write!(f, "{:02x}", *self.data_ptr.add(x))?;
-            }
+        let mut result = String::new();
+        for byte in self.hex_format_bytes(f) {
+            result.push_str(&format!("{:02x}", byte));
         }
 
-        return Ok(());
+        pad_hex(f, &result)
     }
 }
 
 ///
 /// This implementation does not strip leading 0s.
-/// Length of the format result will always be capacity*2
+/// Only the bytes up to the limit are formatted, so the length of the format result is limit*2.
+/// If a precision is given then at most that many bytes are formatted, "{:.8X}" formats the first 8 bytes.
+/// If a width is given then the result is padded with the fill character according to the alignment.
 ///
 impl UpperHex for HBuf {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        unsafe {
-            for x in 0..self.capacity {
-                write!(f, "{:02X}", *self.data_ptr.add(x))?;
-            }
+        let mut result = String::new();
+        for byte in self.hex_format_bytes(f) {
+            result.push_str(&format!("{:02X}", byte));
         }
 
-        return Ok(());
+        pad_hex(f, &result)
+    }
+}
+
+fn pad_hex(f: &mut Formatter<'_>, hex: &str) -> std::fmt::Result {
+    let padding = f.width().unwrap_or(0).saturating_sub(hex.len());
+    let (before, after) = match f.align() {
+        Some(Alignment::Left) => (0, padding),
+        Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(Alignment::Right) | None => (padding, 0)
+    };
+
+    for _ in 0..before {
+        f.write_char(f.fill())?;
     }
+    f.write_str(hex)?;
+    for _ in 0..after {
+        f.write_char(f.fill())?;
+    }
+
+    return Ok(());
 }
 
 
 
+///
+/// Options that control the output of HBuf::hexdump.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexDumpOptions {
+    ///
+    /// Amount of bytes that are printed in each row. Must not be 0.
+    ///
+    pub bytes_per_row: usize,
+    ///
+    /// If true then each row starts with the offset of its first byte relative to the start of the HBuf.
+    /// If false then each row starts with the absolute address of its first byte.
+    ///
+    pub relative_offsets: bool,
+    ///
+    /// If true then each row ends with the printable ascii characters of its bytes.
+    ///
+    pub ascii: bool
+}
+
+///
+/// The default options produce the same rows as the Display implementation of HBuf.
+///
+impl Default for HexDumpOptions {
+    fn default() -> Self {
+        HexDumpOptions {
+            bytes_per_row: 16,
+            relative_offsets: false,
+            ascii: true
+        }
+    }
+}
+
+///
+/// Position and limit of a HBuf as returned by HBuf::save_state.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BufState {
+    ///
+    /// The saved position.
+    ///
+    pub position: usize,
+    ///
+    /// The saved limit.
+    ///
+    pub limit: usize
+}
+
 ///
 /// This formats the "metadata" such as capacity/limit/position/ref-count of the HBuf plus all the data
 /// in a Human-Readable form.
@@ -131,7 +254,7 @@ impl Display for HBuf {
             Position: {}\n\
             Has destructor: {}\n\
             Reference count: {}\n\
-            =============================================================================",
+            =============================================================================\n",
                    self.data_ptr,
                    self.data_ptr.add(self.capacity),
                    self.capacity,
@@ -140,37 +263,8 @@ impl Display for HBuf {
                    self.destructor.is_some(),
                    Arc::strong_count(&self.destructor))?;
 
-
-            for idx_base in (0..self.capacity).step_by(16) {
-                write!(f, "\n0x{:0width$x}:", self.data_ptr.add(idx_base) as usize, width = (usize::BITS / 4) as usize)?;
-
-                for idx in 0..16usize {
-                    if idx & 1 == 0 {
-                        write!(f, " ")?;
-                    }
-                    if idx_base+idx >= self.capacity {
-                        write!(f, "  ")?;
-                        continue;
-                    }
-                    write!(f, "{:02x}", *self.data_ptr.add(idx+idx_base))?;
-                }
-                write!(f, "  ")?;
-
-                for idx in 0..16usize {
-                    if idx_base+idx >= self.capacity {
-                        write!(f, " ")?;
-                        continue;
-                    }
-                    let data = (*self.data_ptr.add(idx+idx_base)) as char;
-                    if char::is_ascii_graphic(&data) {
-                        write!(f, "{}", data)?;
-                    } else {
-                        write!(f, ".")?;
-                    }
-                }
-            }
-
-            write!(f, "\n=============================================================================")?;
+            self.write_hex_dump(f, &HexDumpOptions::default())?;
+            write!(f, "=============================================================================")?;
             return Ok(());
         }
     }
@@ -209,7 +303,7 @@ macro_rules! atomic_type {
         #[inline]
         pub fn $as_atomic(&self, index: usize) -> Option<&$atomic> {
             let sz = size_of::<$atomic>();
-            if index+sz-1 >= self.limit {
+            if !self.in_bounds(index, sz) {
                 return None;
             }
             let ptr = self.data_ptr.wrapping_add(index);
@@ -227,9 +321,7 @@ macro_rules! atomic_type {
         #[inline]
         pub fn $load_name(&self, index: usize, ordering: Ordering) -> $type {
             let sz = size_of::<$atomic>();
-            if index+sz-1 >= self.limit {
-                panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-            }
+            self.check_bounds(index, sz);
             let ptr = self.data_ptr.wrapping_add(index);
             debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
             unsafe {
@@ -243,9 +335,7 @@ macro_rules! atomic_type {
         #[inline]
         pub fn $store_name(&self, index: usize, value: $type, ordering: Ordering) {
             let sz = size_of::<$atomic>();
-            if index+sz-1 >= self.limit {
-                panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-            }
+            self.check_bounds(index, sz);
             let ptr = self.data_ptr.wrapping_add(index);
             debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
             unsafe {
@@ -259,9 +349,7 @@ macro_rules! atomic_type {
         #[inline]
         pub fn $swap_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
             let sz = size_of::<$atomic>();
-            if index+sz-1 >= self.limit {
-                panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-            }
+            self.check_bounds(index, sz);
             let ptr = self.data_ptr.wrapping_add(index);
             debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
             unsafe {
@@ -275,9 +363,7 @@ macro_rules! atomic_type {
         #[inline]
         pub fn $cas_name(&self, index: usize, current: $type, update: $type, success_ordering: Ordering, failure_ordering: Ordering) -> Result<$type, $type> {
             let sz = size_of::<$atomic>();
-            if index+sz-1 >= self.limit {
-                panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-            }
+            self.check_bounds(index, sz);
             let ptr = self.data_ptr.wrapping_add(index);
             debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
             unsafe {
@@ -291,9 +377,7 @@ macro_rules! atomic_type {
         #[inline]
         pub fn $cas_weak_name(&self, index: usize, current: $type, update: $type, success_ordering: Ordering, failure_ordering: Ordering) -> Result<$type, $type> {
             let sz = size_of::<$atomic>();
-            if index+sz-1 >= self.limit {
-                panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-            }
+            self.check_bounds(index, sz);
             let ptr = self.data_ptr.wrapping_add(index);
             debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
             unsafe {
@@ -303,8 +387,136 @@ macro_rules! atomic_type {
     }
 }
 
+macro_rules! atomic_fetch_type {
+    ($type:ty, $atomic:ty, $fetch_add_name:ident, $fetch_sub_name:ident, $fetch_and_name:ident, $fetch_or_name:ident, $fetch_xor_name:ident, $fetch_max_name:ident, $fetch_min_name:ident, $fetch_update_name:ident) => {
+
+        ///
+        /// Atomic "fetch_add" with memory ordering semantics.
+        /// The addition wraps around on overflow.
+        /// Returns the previous value.
+        ///
+        #[inline]
+        pub fn $fetch_add_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_add(value, ordering);
+            }
+        }
+
+        ///
+        /// Atomic "fetch_sub" with memory ordering semantics.
+        /// The subtraction wraps around on overflow.
+        /// Returns the previous value.
+        ///
+        #[inline]
+        pub fn $fetch_sub_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_sub(value, ordering);
+            }
+        }
+
+        ///
+        /// Atomic "fetch_and" with memory ordering semantics.
+        /// Returns the previous value.
+        ///
+        #[inline]
+        pub fn $fetch_and_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_and(value, ordering);
+            }
+        }
+
+        ///
+        /// Atomic "fetch_or" with memory ordering semantics.
+        /// Returns the previous value.
+        ///
+        #[inline]
+        pub fn $fetch_or_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_or(value, ordering);
+            }
+        }
+
+        ///
+        /// Atomic "fetch_xor" with memory ordering semantics.
+        /// Returns the previous value.
+        ///
+        #[inline]
+        pub fn $fetch_xor_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_xor(value, ordering);
+            }
+        }
+
+        ///
+        /// Atomic "fetch_max" with memory ordering semantics.
+        /// Returns the previous value.
+        ///
+        #[inline]
+        pub fn $fetch_max_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_max(value, ordering);
+            }
+        }
+
+        ///
+        /// Atomic "fetch_min" with memory ordering semantics.
+        /// Returns the previous value.
+        ///
+        #[inline]
+        pub fn $fetch_min_name(&self, index: usize, value: $type, ordering: Ordering) -> $type {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_min(value, ordering);
+            }
+        }
+
+        ///
+        /// Atomic "fetch_update" with memory ordering semantics.
+        /// Repeatedly calls the function with the current value until the update succeeds or the function returns None.
+        /// Returns Ok(previous value) if the value was updated, otherwise Err(previous value).
+        ///
+        #[inline]
+        pub fn $fetch_update_name<F: FnMut($type) -> Option<$type>>(&self, index: usize, set_order: Ordering, fetch_order: Ordering, f: F) -> Result<$type, $type> {
+            let sz = size_of::<$atomic>();
+            self.check_bounds(index, sz);
+            let ptr = self.data_ptr.wrapping_add(index);
+            debug_assert_eq!(ptr.align_offset(align_of::<$atomic>()), 0);
+            unsafe {
+                return <$atomic>::from_ptr(ptr.cast::<$type>()).fetch_update(set_order, fetch_order, f);
+            }
+        }
+    }
+}
+
 macro_rules! known_type {
-    ($type:ty, $name:ident, $mut_name:ident, $get_name:ident, $set_name:ident) => {
+    ($type:ty, $name:ident, $mut_name:ident, $get_name:ident, $try_get_name:ident, $get_unchecked_name:ident, $set_name:ident, $try_set_name:ident, $set_unchecked_name:ident) => {
 
         ///
         /// Returns a slice if the HBuf is properly aligned.
@@ -332,28 +544,184 @@ macro_rules! known_type {
         /// panics on out of bounds.
         ///
         pub fn $get_name(&self, index: usize) -> $type {
-            let sz = size_of::<$type>()-1;
-            if index+sz >= self.limit {
-                panic!("Index {} is out of bounds for HBuf with limit {}", index+sz, self.limit);
-            }
+            self.check_bounds(index, size_of::<$type>());
             unsafe { return self.data_ptr.wrapping_add(index).cast::<$type>().read_unaligned(); }
         }
 
         ///
         /// Reads a the value at the given offset.
         /// The value is read using read_unaligned.
+        /// returns None on out of bounds.
+        ///
+        pub fn $try_get_name(&self, index: usize) -> Option<$type> {
+            if !self.in_bounds(index, size_of::<$type>()) {
+                return None;
+            }
+            unsafe { return Some(self.data_ptr.wrapping_add(index).cast::<$type>().read_unaligned()); }
+        }
+
+        ///
+        /// Reads a the value at the given offset without checking bounds.
+        /// The value is read using read_unaligned.
+        ///
+        /// Caller must ensure that index+size_of::<$type>() does not exceed the limit.
+        /// This is only checked by a debug assertion in debug builds.
+        ///
+        #[inline]
+        pub unsafe fn $get_unchecked_name(&self, index: usize) -> $type {
+            debug_assert!(self.in_bounds(index, size_of::<$type>()), "Index {} is out of bounds for HBuf with limit {}", index, self.limit);
+            self.data_ptr.wrapping_add(index).cast::<$type>().read_unaligned()
+        }
+
+        ///
+        /// Writes the value at the given offset.
+        /// The value is written using write_unaligned.
         /// panics on out of bounds.
         ///
-        pub fn $set_name<T: Sized>(&mut self, index: usize, value: $type) {
-            let sz = size_of::<$type>()-1;
-            if index+sz >= self.limit {
-                panic!("Index {} is out of bounds for HBuf with limit {}", index+sz, self.limit);
+        pub fn $set_name(&mut self, index: usize, value: $type) {
+            self.check_bounds(index, size_of::<$type>());
+            unsafe { self.data_ptr.wrapping_add(index).cast::<$type>().write_unaligned(value); }
+        }
+
+        ///
+        /// Writes the value at the given offset.
+        /// The value is written using write_unaligned.
+        /// returns HBufError::OutOfBounds on out of bounds.
+        ///
+        pub fn $try_set_name(&mut self, index: usize, value: $type) -> Result<(), HBufError> {
+            if !self.in_bounds(index, size_of::<$type>()) {
+                return Err(HBufError::OutOfBounds { index, limit: self.limit });
             }
             unsafe { self.data_ptr.wrapping_add(index).cast::<$type>().write_unaligned(value); }
+            Ok(())
+        }
+
+        ///
+        /// Writes the value at the given offset without checking bounds.
+        /// The value is written using write_unaligned.
+        ///
+        /// Caller must ensure that index+size_of::<$type>() does not exceed the limit.
+        /// This is only checked by a debug assertion in debug builds.
+        ///
+        #[inline]
+        pub unsafe fn $set_unchecked_name(&mut self, index: usize, value: $type) {
+            debug_assert!(self.in_bounds(index, size_of::<$type>()), "Index {} is out of bounds for HBuf with limit {}", index, self.limit);
+            self.data_ptr.wrapping_add(index).cast::<$type>().write_unaligned(value);
+        }
+    };
+}
+
+macro_rules! stream_type {
+    ($type:ty, $read_le_name:ident, $read_be_name:ident, $write_le_name:ident, $write_be_name:ident) => {
+
+        ///
+        /// Reads a little endian value at the position and advances the position by its size.
+        /// Returns an UnexpectedEof error and leaves the position unchanged if less bytes remain before the limit.
+        ///
+        pub fn $read_le_name(&mut self) -> io::Result<$type> {
+            let mut bytes = [0u8; size_of::<$type>()];
+            self.read_exact(&mut bytes)?;
+            Ok(<$type>::from_le_bytes(bytes))
+        }
+
+        ///
+        /// Reads a big endian value at the position and advances the position by its size.
+        /// Returns an UnexpectedEof error and leaves the position unchanged if less bytes remain before the limit.
+        ///
+        pub fn $read_be_name(&mut self) -> io::Result<$type> {
+            let mut bytes = [0u8; size_of::<$type>()];
+            self.read_exact(&mut bytes)?;
+            Ok(<$type>::from_be_bytes(bytes))
+        }
+
+        ///
+        /// Writes a value as little endian at the position and advances the position by its size.
+        /// Returns an UnexpectedEof error and leaves the position unchanged if less bytes remain before the limit.
+        ///
+        pub fn $write_le_name(&mut self, value: $type) -> io::Result<()> {
+            self.write_all(&value.to_le_bytes())
+        }
+
+        ///
+        /// Writes a value as big endian at the position and advances the position by its size.
+        /// Returns an UnexpectedEof error and leaves the position unchanged if less bytes remain before the limit.
+        ///
+        pub fn $write_be_name(&mut self, value: $type) -> io::Result<()> {
+            self.write_all(&value.to_be_bytes())
+        }
+    };
+}
+
+macro_rules! endian_type {
+    ($type:ty, $get_le_name:ident, $get_be_name:ident, $set_le_name:ident, $set_be_name:ident) => {
+
+        ///
+        /// Reads a little endian value at the given offset.
+        /// This does not use or modify the position.
+        /// panics on out of bounds.
+        ///
+        pub fn $get_le_name(&self, index: usize) -> $type {
+            self.check_bounds(index, size_of::<$type>());
+            unsafe { <$type>::from_le_bytes(self.data_ptr.wrapping_add(index).cast::<[u8; size_of::<$type>()]>().read()) }
+        }
+
+        ///
+        /// Reads a big endian value at the given offset.
+        /// This does not use or modify the position.
+        /// panics on out of bounds.
+        ///
+        pub fn $get_be_name(&self, index: usize) -> $type {
+            self.check_bounds(index, size_of::<$type>());
+            unsafe { <$type>::from_be_bytes(self.data_ptr.wrapping_add(index).cast::<[u8; size_of::<$type>()]>().read()) }
+        }
+
+        ///
+        /// Writes a value as little endian at the given offset.
+        /// This does not use or modify the position.
+        /// panics on out of bounds.
+        ///
+        pub fn $set_le_name(&mut self, index: usize, value: $type) {
+            self.check_bounds(index, size_of::<$type>());
+            unsafe { self.data_ptr.wrapping_add(index).cast::<[u8; size_of::<$type>()]>().write(value.to_le_bytes()) }
+        }
+
+        ///
+        /// Writes a value as big endian at the given offset.
+        /// This does not use or modify the position.
+        /// panics on out of bounds.
+        ///
+        pub fn $set_be_name(&mut self, index: usize, value: $type) {
+            self.check_bounds(index, size_of::<$type>());
+            unsafe { self.data_ptr.wrapping_add(index).cast::<[u8; size_of::<$type>()]>().write(value.to_be_bytes()) }
         }
     };
 }
 
+macro_rules! swap_type {
+    ($type:ty, $name:ident) => {
+
+        ///
+        /// Reverses the byte order of every element when the HBuf is interpreted as a slice of the type.
+        /// This is useful to convert big endian data to little endian data and vice versa.
+        ///
+        /// panics if the HBuf is not properly aligned or the limit is not a multiple of the size of the type.
+        ///
+        pub fn $name(&mut self) {
+            if self.data_ptr.align_offset(align_of::<$type>()) != 0 {
+                panic!("HBuf is not properly aligned for {}", align_of::<$type>());
+            }
+
+            if self.limit % size_of::<$type>() != 0 {
+                panic!("Limit {} of HBuf is not a multiple of {}", self.limit, size_of::<$type>());
+            }
+
+            let slice = unsafe { std::slice::from_raw_parts_mut(self.data_ptr.inner().cast::<$type>(), self.limit / size_of::<$type>()) };
+            for element in slice.iter_mut() {
+                *element = element.swap_bytes();
+            }
+        }
+    };
+}
 
 ///
 /// Trait to allow implementing a custom Destructor in rust.
@@ -370,17 +738,91 @@ pub trait DynDestructor: Send+Sync+Debug+RefUnwindSafe+UnwindSafe {
 impl HBuf {
 
     ///
-    /// Creates a HBuf from a pointer.
-    /// Dropping the resulting HBuf is a noop.
-    /// Caller must ensure that the pointer lives longer than HBuf and is valid.
+    /// Returns true if the range index..index+size lies within the limit.
+    /// This check cannot overflow.
     ///
-    pub unsafe fn from_raw_parts(data: *mut u8, size: usize) -> HBuf {
-        HBuf {
-            data_ptr: data.as_sync_mut(),
-            capacity: size,
-            limit: size,
-            position: 0,
-            destructor: Arc::new(None)
+    #[inline]
+    fn in_bounds(&self, index: usize, size: usize) -> bool {
+        match index.checked_add(size) {
+            Some(end) => end <= self.limit,
+            None => false
+        }
+    }
+
+    ///
+    /// panics if the range index..index+size does not lie within the limit.
+    /// This check cannot overflow.
+    ///
+    #[inline]
+    fn check_bounds(&self, index: usize, size: usize) {
+        match index.checked_add(size) {
+            Some(end) => if end > self.limit {
+                panic!("Index {} is out of bounds for HBuf with limit {}", end-1, self.limit);
+            },
+            None => panic!("Index {} with size {} overflows and is out of bounds for HBuf with limit {}", index, size, self.limit)
+        }
+    }
+
+    ///
+    /// Resolves a range to a start (inclusive) and end (exclusive) index.
+    /// panics if the range does not lie within the limit.
+    ///
+    #[inline]
+    fn check_range<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.checked_add(1).unwrap_or_else(|| panic!("Range start {} overflows", start)),
+            Bound::Unbounded => 0
+        };
+
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.checked_add(1).unwrap_or_else(|| panic!("Range end {} overflows", end)),
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.limit
+        };
+
+        if start > end {
+            panic!("Range start {} is greater than range end {}", start, end);
+        }
+
+        if end > self.limit {
+            panic!("Range end {} is out of bounds for HBuf with limit {}", end, self.limit);
+        }
+
+        (start, end)
+    }
+
+    ///
+    /// Creates a HBuf with a capacity of 0 that does not allocate any memory, similar to Vec::new.
+    /// The pointer of the HBuf is dangling but aligned for all primitive types, so all slices of it are empty.
+    /// Dropping the resulting HBuf is a noop.
+    ///
+    pub fn empty() -> HBuf {
+        HBuf {
+            data_ptr: unsafe { NonNull::<u128>::dangling().as_ptr().cast::<u8>().as_sync_mut() },
+            capacity: 0,
+            limit: 0,
+            position: 0,
+            mark: None,
+            destructor: Arc::new(None),
+            shared_limit: None
+        }
+    }
+
+    ///
+    /// Creates a HBuf from a pointer.
+    /// Dropping the resulting HBuf is a noop.
+    /// Caller must ensure that the pointer lives longer than HBuf and is valid.
+    ///
+    pub unsafe fn from_raw_parts(data: *mut u8, size: usize) -> HBuf {
+        HBuf {
+            data_ptr: data.as_sync_mut(),
+            capacity: size,
+            limit: size,
+            position: 0,
+            mark: None,
+            destructor: Arc::new(None),
+            shared_limit: None
         }
     }
 
@@ -396,7 +838,9 @@ impl HBuf {
             capacity: size,
             limit: size,
             position: 0,
-            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::Destructor(destructor))))
+            mark: None,
+            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::Destructor(destructor)))),
+            shared_limit: None
         }
     }
 
@@ -412,7 +856,9 @@ impl HBuf {
             capacity: size,
             limit: size,
             position: 0,
-            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::DynDestructor(destructor))))
+            mark: None,
+            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::DynDestructor(destructor)))),
+            shared_limit: None
         }
     }
 
@@ -434,6 +880,24 @@ impl HBuf {
         HBuf::allocate_aligned_zeroed(size, 1)
     }
 
+    ///
+    /// Allocates the given amount of zeroed memory with no particular alignment.
+    /// The memory is overwritten with zeros before it is deallocated once no more references to the HBuf exist.
+    /// This is intended for sensitive data such as cryptographic keys.
+    ///
+    /// The memory of a HBuf allocated by this function can never be resized.
+    ///
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    /// (It calls std::alloc::handle_alloc_error on out of memory)
+    ///
+    pub fn allocate_secure(size: usize) -> HBuf {
+        let mut buf = HBuf::allocate_zeroed(size);
+        if let Some(Some(destructor)) = Arc::get_mut(&mut buf.destructor) {
+            destructor.make_secure();
+        }
+        buf
+    }
+
     ///
     /// Allocates the given mount of memory with the given alignment.
     /// This function panics if the alignment is invalid.
@@ -441,9 +905,7 @@ impl HBuf {
     /// (It calls std::alloc::handle_alloc_error on out of memory)
     ///
     pub fn allocate_aligned_zeroed(size: usize, alignment: usize) -> HBuf {
-        let mut buf =  HBuf::allocate_aligned(size, alignment);
-        buf.fill(0);
-        buf
+        HBuf::allocate_layout(size, alignment, true)
     }
 
     ///
@@ -452,8 +914,83 @@ impl HBuf {
     /// This function panics/aborts if the amount of memory could not be allocated.
     /// (It calls std::alloc::handle_alloc_error on out of memory)
     ///
-    #[allow(unreachable_code)]
     pub fn allocate_aligned(size: usize, alignment: usize) -> HBuf {
+        HBuf::allocate_layout(size, alignment, false)
+    }
+
+    ///
+    /// Returns the size of a cache line of the target in bytes.
+    /// This is 128 on apple silicon and 64 on all other targets.
+    ///
+    pub const fn cache_line_size() -> usize {
+        if cfg!(all(target_arch = "aarch64", target_vendor = "apple")) {
+            return 128;
+        }
+
+        64
+    }
+
+    ///
+    /// Allocates the given amount of memory aligned to the cache line size.
+    /// Values that are placed cache_line_size() bytes apart never share a cache line,
+    /// which avoids false sharing between threads that modify them concurrently.
+    /// This function panics if the size is 0.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn allocate_cache_aligned(size: usize) -> HBuf {
+        HBuf::allocate_aligned(size, HBuf::cache_line_size())
+    }
+
+    ///
+    /// Allocates the given amount of zeroed memory aligned to the cache line size, see allocate_cache_aligned.
+    /// This function panics if the size is 0.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn allocate_cache_aligned_zeroed(size: usize) -> HBuf {
+        HBuf::allocate_aligned_zeroed(size, HBuf::cache_line_size())
+    }
+
+    ///
+    /// Allocates a new HBuf with a capacity of data.len() with no particular alignment and copies data into it.
+    /// If data is empty then HBuf::empty is returned.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn from_slice(data: &[u8]) -> HBuf {
+        if data.is_empty() {
+            return HBuf::empty();
+        }
+
+        HBuf::from_slice_aligned(data, 1)
+    }
+
+    ///
+    /// Allocates a new HBuf with a capacity of data.len() with the given alignment and copies data into it.
+    /// If data is empty then an empty HBuf like HBuf::empty is returned whose dangling pointer is aligned to the alignment.
+    /// This function panics if the alignment is invalid.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn from_slice_aligned(data: &[u8], alignment: usize) -> HBuf {
+        if data.is_empty() {
+            if Layout::from_size_align(0, alignment).is_err() {
+                panic!("LayoutError when creating layout for size 0 alignment {}", alignment);
+            }
+
+            let mut buf = HBuf::empty();
+            buf.data_ptr = unsafe { (alignment.max(align_of::<u128>()) as *mut u8).as_sync_mut() };
+            return buf;
+        }
+
+        let mut buf = HBuf::allocate_aligned(data.len(), alignment);
+        buf.copy_from_slice(0, data);
+        buf
+    }
+
+    ///
+    /// Allocates memory with std::alloc::alloc or std::alloc::alloc_zeroed if zeroed is true.
+    /// alloc_zeroed can avoid touching every page for large allocations because fresh pages from the OS are already zero.
+    ///
+    #[allow(unreachable_code)]
+    fn allocate_layout(size: usize, alignment: usize, zeroed: bool) -> HBuf {
         if size == 0 {
             panic!("size is 0");
         }
@@ -467,7 +1004,12 @@ impl HBuf {
             panic!("LayoutError when creating layout for size {} alignment {}", size, alignment);
         }
         let layout = layout.unwrap();
-        let data = unsafe {std::alloc::alloc(layout)};
+        let data = unsafe {
+            match zeroed {
+                true => std::alloc::alloc_zeroed(layout),
+                false => std::alloc::alloc(layout)
+            }
+        };
         if data.is_null() {
             std::alloc::handle_alloc_error(layout);
             panic!("handle_alloc_error failed to panic or abort after OutOfMemory!");
@@ -480,7 +1022,9 @@ impl HBuf {
             capacity: size,
             limit: size,
             position: 0,
-            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::Layout(layout))))
+            mark: None,
+            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::Layout(layout)))),
+            shared_limit: None
         }
     }
 
@@ -499,9 +1043,7 @@ impl HBuf {
     /// If the allocation is successful then it is zeroed out.
     ///
     pub fn try_allocate_zeroed(size: usize) -> Result<HBuf, HBufError> {
-        let mut buf = HBuf::try_allocate_aligned(size, 1)?;
-        buf.fill(0);
-        Ok(buf)
+        HBuf::try_allocate_aligned_zeroed(size, 1)
     }
 
     ///
@@ -513,9 +1055,7 @@ impl HBuf {
     /// If the allocation is successful then it is zeroed out.
     ///
     pub fn try_allocate_aligned_zeroed(size: usize, alignment: usize) -> Result<HBuf, HBufError> {
-        let mut buf = HBuf::try_allocate_aligned(size, alignment)?;
-        buf.fill(0);
-        Ok(buf)
+        HBuf::try_allocate_layout(size, alignment, true)
     }
 
     ///
@@ -526,12 +1066,24 @@ impl HBuf {
     ///
     ///
     pub fn try_allocate_aligned(size: usize, alignment: usize) -> Result<HBuf, HBufError> {
+        HBuf::try_allocate_layout(size, alignment, false)
+    }
+
+    ///
+    /// Allocates memory with std::alloc::alloc or std::alloc::alloc_zeroed if zeroed is true.
+    ///
+    fn try_allocate_layout(size: usize, alignment: usize, zeroed: bool) -> Result<HBuf, HBufError> {
         if size == 0 || alignment == 0 {
             return Err(HBufError::LayoutError);
         }
 
         let layout = Layout::from_size_align(size, alignment)?;
-        let data = unsafe {std::alloc::alloc(layout)};
+        let data = unsafe {
+            match zeroed {
+                true => std::alloc::alloc_zeroed(layout),
+                false => std::alloc::alloc(layout)
+            }
+        };
         if data.is_null() {
             return Err(HBufError::OutOfMemory);
         }
@@ -548,12 +1100,250 @@ impl HBuf {
             capacity: size,
             limit: size,
             position: 0,
-            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::Layout(layout))))
+            mark: None,
+            destructor: Arc::new(Some(HBufDestructor::new(data, size, HBufDestructorInfo::Layout(layout)))),
+            shared_limit: None
         })
     }
 
 
 
+    ///
+    /// Resizes the memory of the HBuf using std::alloc::realloc. The alignment of the memory is preserved.
+    /// The contents of the buffer up to the smaller of the old and new capacity are preserved.
+    /// If the buffer grows then the contents of the new memory are undefined.
+    ///
+    /// If the limit was equal to the capacity then the limit is set to the new capacity.
+    /// Otherwise, limit, position and mark are reduced to the new capacity if they exceed it.
+    /// The shared atomic limit is also reduced to the new capacity if it exceeds it.
+    ///
+    /// This function fails if this HBuf is shared with other references (ref_count > 1)
+    /// or if its memory was not allocated by one of the allocate functions of HBuf.
+    /// Sub buffers created by split can never be resized.
+    ///
+    /// This invalidates all slices and pointers obtained prior to calling this method.
+    ///
+    pub fn try_resize(&mut self, new_size: usize) -> Result<(), HBufError> {
+        if new_size == 0 {
+            return Err(HBufError::ZeroSize);
+        }
+
+        let data_ptr = self.data_ptr;
+        let capacity = self.capacity;
+        let destructor = match Arc::get_mut(&mut self.destructor) {
+            Some(Some(destructor)) => destructor,
+            Some(None) => return Err(HBufError::NotOwned),
+            None => return Err(HBufError::Shared)
+        };
+
+        if !destructor.owns_allocation(data_ptr, capacity) {
+            return Err(HBufError::NotOwned);
+        }
+
+        self.data_ptr = destructor.realloc(new_size)?;
+
+        if self.limit == self.capacity || self.limit > new_size {
+            self.limit = new_size;
+        }
+
+        self.capacity = new_size;
+
+        if let Some(shared_limit) = &self.shared_limit {
+            shared_limit.fetch_min(new_size, Ordering::SeqCst);
+        }
+
+        if self.position > self.limit {
+            self.position = self.limit;
+        }
+
+        if self.mark.is_some_and(|mark| mark > self.limit) {
+            self.mark = None;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Shrinks the memory of the HBuf so that the capacity is equal to the limit, freeing the memory after the limit.
+    /// This is a noop if the limit is already equal to the capacity.
+    ///
+    /// This function fails with the same errors as try_resize and additionally with ZeroSize if the limit is 0.
+    ///
+    /// This invalidates all slices and pointers obtained prior to calling this method.
+    ///
+    pub fn shrink_to_fit(&mut self) -> Result<(), HBufError> {
+        if self.limit == self.capacity {
+            return Ok(());
+        }
+
+        self.try_resize(self.limit)
+    }
+
+    ///
+    /// Reclaims the allocation of a HBuf that was created from a boxed slice or Vec<u8> without copying.
+    /// The length of the boxed slice is the limit of the HBuf.
+    /// If the limit is smaller than the capacity then the allocation is shrunk to the limit.
+    /// If other references to the memory exist or the HBuf was not created from a boxed slice or Vec
+    /// then the HBuf is returned unchanged.
+    ///
+    pub fn into_boxed_slice(mut self) -> Result<Box<[u8]>, HBuf> {
+        match self.take_vec() {
+            Ok(vec) => Ok(vec.into_boxed_slice()),
+            Err(_) => Err(self)
+        }
+    }
+
+    ///
+    /// Returns the pointer, capacity and limit of the HBuf and ensures that the destructor of the memory never runs.
+    /// This is intended for handing the memory over to foreign code.
+    ///
+    /// If this is the only reference to the memory then the caller becomes responsible for freeing it.
+    /// Memory obtained from allocate* must be freed with std::alloc::dealloc using a Layout with the same capacity
+    /// and alignment, memory obtained from a Vec<u8> or boxed slice must be turned back into a Vec with the returned capacity.
+    /// Freeing the memory any other way is undefined behavior, not freeing it is a memory leak.
+    /// If the HBuf was split off another HBuf then the pointer is not the start of the allocation and must not be freed.
+    ///
+    /// If other references to the memory exist then they remain valid but the memory is leaked once all of them are dropped.
+    ///
+    pub fn into_raw_parts(mut self) -> (*mut u8, usize, usize) {
+        match Arc::get_mut(&mut self.destructor) {
+            Some(slot) => std::mem::forget(slot.take()),
+            None => std::mem::forget(self.destructor.clone())
+        }
+
+        (self.data_ptr.inner(), self.capacity, self.limit)
+    }
+
+    ///
+    /// Leaks the memory of the HBuf and returns a slice of its contents up to the limit that lives forever.
+    /// The destructor of the memory never runs.
+    ///
+    /// If other references (clones or splits) to the memory exist then the HBuf is returned unchanged,
+    /// since they would alias the returned slice.
+    ///
+    pub fn leak(self) -> Result<&'static mut [u8], HBuf> {
+        if !self.is_unique() {
+            return Err(self);
+        }
+
+        let (data, _, limit) = self.into_raw_parts();
+        unsafe { Ok(std::slice::from_raw_parts_mut(data, limit)) }
+    }
+
+    ///
+    /// Takes the allocation out of a HBuf that uniquely owns memory taken from a Vec<u8>.
+    /// The HBuf is left without a destructor on success and unchanged on failure.
+    ///
+    fn take_vec(&mut self) -> Result<Vec<u8>, HBufError> {
+        let data_ptr = self.data_ptr;
+        let capacity = self.capacity;
+        let slot = match Arc::get_mut(&mut self.destructor) {
+            Some(slot) => slot,
+            None => return Err(HBufError::Shared)
+        };
+
+        if !slot.as_ref().is_some_and(|destructor| destructor.owns_vec(data_ptr, capacity)) {
+            return Err(HBufError::NotOwned);
+        }
+
+        let destructor = slot.take().unwrap();
+        Ok(unsafe { destructor.into_vec(self.limit) })
+    }
+
+    ///
+    /// Creates a copy of this HBuf that is backed by a newly allocated memory.
+    /// All bytes up to the capacity as well as limit, position and mark are copied.
+    /// The copy has the same alignment as this HBuf and a reference count of 1.
+    /// If this HBuf was allocated by allocate_secure then so is the copy.
+    /// If the capacity is 0 then HBuf::empty is returned.
+    ///
+    /// This differs from clone which does not copy the memory and only creates another reference to the same memory.
+    ///
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn deep_clone(&self) -> HBuf {
+        if self.capacity == 0 {
+            return HBuf::empty();
+        }
+
+        let mut copy = HBuf::allocate_aligned(self.capacity, self.natural_alignment());
+        unsafe { std::ptr::copy_nonoverlapping(self.data_ptr.inner(), copy.data_ptr.inner(), self.capacity) }
+        copy.limit = self.limit;
+        copy.position = self.position;
+        copy.mark = self.mark;
+
+        if self.destructor_ref().is_some_and(|destructor| destructor.is_secure()) {
+            if let Some(Some(destructor)) = Arc::get_mut(&mut copy.destructor) {
+                destructor.make_secure();
+            }
+        }
+
+        copy
+    }
+
+    ///
+    /// Returns a HBuf whose memory is aligned to the given alignment and contains the bytes up to the limit.
+    /// If this HBuf is already aligned then a clone is returned that shares the memory with this HBuf.
+    /// Otherwise a new HBuf with a capacity of limit is allocated and the bytes up to the limit are copied into it.
+    /// The position is preserved in both cases.
+    ///
+    /// This function panics if the alignment is invalid or if the limit is 0 and the HBuf is not aligned.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn realign(&self, alignment: usize) -> HBuf {
+        if self.is_aligned_to(alignment) {
+            return self.clone();
+        }
+
+        let mut copy = HBuf::allocate_aligned(self.limit, alignment);
+        copy.copy_from_slice(0, self.as_slice());
+        copy.position = self.position;
+        copy
+    }
+
+    ///
+    /// Returns the alignment that this HBuf was allocated with.
+    /// If it is unknown then the largest power of 2 (up to 4096) that the pointer is aligned to is returned.
+    ///
+    fn natural_alignment(&self) -> usize {
+        if let Some(alignment) = self.destructor_ref().and_then(|destructor| destructor.layout_alignment()) {
+            if self.data_ptr.align_offset(alignment) == 0 {
+                return alignment;
+            }
+        }
+
+        self.max_alignment().min(4096)
+    }
+
+    ///
+    /// Returns true if the start of the memory of this HBuf is aligned to the given alignment.
+    /// panics if the alignment is not a power of 2.
+    ///
+    pub fn is_aligned_to(&self, alignment: usize) -> bool {
+        self.data_ptr.align_offset(alignment) == 0
+    }
+
+    ///
+    /// Returns the largest power of 2 alignment that the start of the memory of this HBuf is aligned to.
+    /// This may be larger than the alignment the HBuf was allocated with.
+    ///
+    pub fn max_alignment(&self) -> usize {
+        let address = self.data_ptr.inner() as usize;
+        if address == 0 {
+            return 1 << (usize::BITS - 1);
+        }
+
+        1 << address.trailing_zeros()
+    }
+
+    ///
+    /// Overwrites the entire capacity of the HBuf with zeros using volatile writes
+    /// so that the compiler cannot elide the writes.
+    ///
+    pub fn zeroize(&mut self) {
+        unsafe { secure_zero(self.data_ptr.inner(), self.capacity) }
+    }
+
     ///
     /// Returns the reference count of the HBuf.
     ///
@@ -561,11 +1351,41 @@ impl HBuf {
         Arc::strong_count(&self.destructor)
     }
 
+    ///
+    /// Returns true if this is the only reference to the memory of this HBuf.
+    ///
+    pub fn is_unique(&self) -> bool {
+        self.ref_count() == 1
+    }
+
+    ///
+    /// Returns a mutable slice that is backed by the HBuf.
+    /// The size of the slice is the current limit.
+    /// If the memory is shared with other HBuf's then a deep_clone is made first and this HBuf is replaced with it.
+    /// Writes to the returned slice are therefore never visible to any other HBuf.
+    ///
+    /// This function panics/aborts if the copy could not be allocated.
+    ///
+    pub fn make_mut(&mut self) -> &mut [u8] {
+        if !self.is_unique() {
+            *self = self.deep_clone();
+        }
+
+        unsafe { std::slice::from_raw_parts_mut(self.data_ptr.inner(), self.limit) }
+    }
+
     ///
     /// Returns true if this HBuf has a destructor that will run when all references to the HBuf are dropped.
     ///
     pub fn has_destructor(&self) -> bool {
-        self.destructor.is_none()
+        self.destructor.is_some()
+    }
+
+    ///
+    /// Returns the destructor of the memory if there is one.
+    ///
+    pub(crate) fn destructor_ref(&self) -> Option<&HBufDestructor> {
+        self.destructor.as_ref().as_ref()
     }
 
     ///
@@ -605,6 +1425,30 @@ impl HBuf {
         self.data_ptr.inner()
     }
 
+    ///
+    /// Returns the pointer to the byte at the given index.
+    /// The index may be equal to the limit to obtain a pointer one past the last accessible byte.
+    ///
+    /// panics if index > limit.
+    ///
+    pub fn as_ptr_at(&self, index: usize) -> *const u8 {
+        self.as_mut_ptr_at(index)
+    }
+
+    ///
+    /// Returns the mutable pointer to the byte at the given index.
+    /// The index may be equal to the limit to obtain a pointer one past the last accessible byte.
+    ///
+    /// panics if index > limit.
+    ///
+    pub fn as_mut_ptr_at(&self, index: usize) -> *mut u8 {
+        if index > self.limit {
+            panic!("Index {} is out of bounds for HBuf with limit {}", index, self.limit);
+        }
+
+        self.data_ptr.wrapping_add(index)
+    }
+
     ///
     /// Returns a slice that is backed by the HBuf.
     /// The size of the slice is the current limit.
@@ -617,7 +1461,7 @@ impl HBuf {
     /// Returns a mutable slice that is backed by the HBuf.
     /// The size of the slice is the current limit.
     ///
-    pub fn as_mut_slice(&self) -> &mut [u8] {
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
         unsafe { std::slice::from_raw_parts_mut(self.data_ptr.inner(), self.limit) }
     }
 
@@ -636,6 +1480,9 @@ impl HBuf {
     /// Turns this HBuf into a mutable slice of arbitrary data.
     /// This function will return None if the alignment of T does not match the alignment of the HBuf
     ///
+    /// Caller must ensure that no other reference to the memory of the HBuf exists while the slice is alive,
+    /// since this function only borrows the HBuf immutably.
+    ///
     pub unsafe fn as_mut_slice_generic<T: Sized>(&self) -> Option<&mut [T]> {
         if self.data_ptr.align_offset(align_of::<T>()) != 0 {
             return None;
@@ -644,31 +1491,66 @@ impl HBuf {
     }
 
     ///
-    /// Copies the value T at the specified location out of the memory.
-    /// This method uses read_unaligned so alignment is irrelevant for this method.
+    /// Turns this HBuf into a slice of arbitrary data without ignoring trailing bytes.
+    /// This function will return None if the alignment of T does not match the alignment of the HBuf
+    /// or if the limit is not a multiple of the size of T.
     ///
-    pub unsafe fn get<T: Sized+Copy>(&self, index: usize) -> T {
-        let sz = size_of::<T>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuffer with limit {}", index+sz-1, self.limit);
+    pub unsafe fn as_slice_exact<T: Sized>(&self) -> Option<&[T]> {
+        if size_of::<T>() == 0 || self.limit % size_of::<T>() != 0 {
+            return None;
         }
-        unsafe { self.data_ptr.wrapping_add(index).cast::<T>().read_unaligned() }
+        self.as_slice_generic()
     }
 
     ///
-    /// Returns a reference to a datatype stored at the given location in memory.
-    /// This method is unsafe because it will always return a reference regardless of borrow checking/multithreading
-    /// constraints that the type T may require.
+    /// Turns this HBuf into a mutable slice of arbitrary data without ignoring trailing bytes.
+    /// This function will return None if the alignment of T does not match the alignment of the HBuf
+    /// or if the limit is not a multiple of the size of T.
     ///
-    /// This function enforces alignment of T and will panic if the memory is not properly aligned.
+    /// Caller must ensure the same as for as_mut_slice_generic.
     ///
-    pub unsafe fn get_ref<T>(&self, index: usize) -> &T {
-        let sz = size_of::<T>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HeapBuffer with limit {}", index+sz-1, self.limit);
+    pub unsafe fn as_mut_slice_exact<T: Sized>(&self) -> Option<&mut [T]> {
+        if size_of::<T>() == 0 || self.limit % size_of::<T>() != 0 {
+            return None;
         }
+        self.as_mut_slice_generic()
+    }
 
-        let ptr = self.data_ptr.wrapping_add(index);
+    ///
+    /// Copies the value T at the specified location out of the memory.
+    /// This method uses read_unaligned so alignment is irrelevant for this method.
+    ///
+    pub unsafe fn get<T: Sized+Copy>(&self, index: usize) -> T {
+        let sz = size_of::<T>();
+        self.check_bounds(index, sz);
+        unsafe { self.data_ptr.wrapping_add(index).cast::<T>().read_unaligned() }
+    }
+
+    ///
+    /// Copies the value T at the specified location out of the memory.
+    /// This method uses read_unaligned so alignment is irrelevant for this method.
+    ///
+    /// returns None on out of bounds.
+    ///
+    pub unsafe fn try_get<T: Sized+Copy>(&self, index: usize) -> Option<T> {
+        if !self.in_bounds(index, size_of::<T>()) {
+            return None;
+        }
+        unsafe { Some(self.data_ptr.wrapping_add(index).cast::<T>().read_unaligned()) }
+    }
+
+    ///
+    /// Returns a reference to a datatype stored at the given location in memory.
+    /// This method is unsafe because it will always return a reference regardless of borrow checking/multithreading
+    /// constraints that the type T may require.
+    ///
+    /// This function enforces alignment of T and will panic if the memory is not properly aligned.
+    ///
+    pub unsafe fn get_ref<T>(&self, index: usize) -> &T {
+        let sz = size_of::<T>();
+        self.check_bounds(index, sz);
+
+        let ptr = self.data_ptr.wrapping_add(index);
         if ptr.align_offset(align_of::<T>()) != 0 {
             panic!("Index {} is not properly aligned for {}", index+sz-1, align_of::<T>());
         }
@@ -676,6 +1558,21 @@ impl HBuf {
         ptr.cast::<T>().as_ref().unwrap()
     }
 
+    ///
+    /// Returns a reference to a datatype stored at the given location in memory without checking bounds or alignment.
+    ///
+    /// Caller must ensure that index+size_of::<T>() does not exceed the limit and that the memory at index is properly
+    /// aligned for T. The same borrow checking/multithreading constraints as for get_ref apply.
+    /// This is only checked by debug assertions in debug builds.
+    ///
+    #[inline]
+    pub unsafe fn get_ref_unchecked<T>(&self, index: usize) -> &T {
+        debug_assert!(self.in_bounds(index, size_of::<T>()), "Index {} is out of bounds for HBuf with limit {}", index, self.limit);
+        let ptr = self.data_ptr.wrapping_add(index);
+        debug_assert_eq!(ptr.align_offset(align_of::<T>()), 0);
+        &*ptr.cast::<T>()
+    }
+
     ///
     /// Returns a reference to a datatype stored at the given location in memory.
     /// This method is unsafe because it will always return a reference regardless of borrow checking/multithreading
@@ -685,9 +1582,7 @@ impl HBuf {
     ///
     pub unsafe fn get_ref_mut<T>(&self, index: usize) -> &mut T {
         let sz = size_of::<T>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-        }
+        self.check_bounds(index, sz);
 
         let ptr = self.data_ptr.wrapping_add(index);
         if ptr.align_offset(align_of::<T>()) != 0 {
@@ -697,6 +1592,102 @@ impl HBuf {
         ptr.cast::<T>().as_mut().unwrap()
     }
 
+    ///
+    /// Returns a reference to a datatype stored at the start of the HBuf, for example the header struct of a C file format.
+    /// Returns None if the memory is not properly aligned for T or if the size of T exceeds the limit.
+    ///
+    /// This method is unsafe for the same reasons as get_ref. Additionally, the caller must ensure that every bit pattern
+    /// stored in the memory is a valid value of T, which usually means T should be a #[repr(C)] struct of integers.
+    /// Padding bytes of T are not guaranteed to be preserved when the returned reference is written to.
+    ///
+    pub unsafe fn as_ref_struct<T>(&self) -> Option<&T> {
+        if size_of::<T>() > self.limit || self.data_ptr.align_offset(align_of::<T>()) != 0 {
+            return None;
+        }
+
+        self.data_ptr.inner().cast::<T>().as_ref()
+    }
+
+    ///
+    /// Returns a mutable reference to a datatype stored at the start of the HBuf.
+    /// Returns None if the memory is not properly aligned for T or if the size of T exceeds the limit.
+    ///
+    /// This method is unsafe for the same reasons as as_ref_struct and get_ref_mut.
+    ///
+    pub unsafe fn as_mut_struct<T>(&self) -> Option<&mut T> {
+        if size_of::<T>() > self.limit || self.data_ptr.align_offset(align_of::<T>()) != 0 {
+            return None;
+        }
+
+        self.data_ptr.inner().cast::<T>().as_mut()
+    }
+
+    ///
+    /// Reads the value T at the given location using a volatile read that the compiler will not elide or reorder
+    /// with other volatile accesses. This is intended for memory mapped device registers.
+    ///
+    /// This function enforces alignment of T and will panic if the memory is not properly aligned.
+    ///
+    pub unsafe fn read_volatile<T: Copy>(&self, index: usize) -> T {
+        self.check_bounds(index, size_of::<T>());
+
+        let ptr = self.data_ptr.wrapping_add(index);
+        if ptr.align_offset(align_of::<T>()) != 0 {
+            panic!("Index {} is not properly aligned for {}", index, align_of::<T>());
+        }
+
+        ptr.cast::<T>().read_volatile()
+    }
+
+    ///
+    /// Writes the value T to the given location using a volatile write that the compiler will not elide or reorder
+    /// with other volatile accesses. This is intended for memory mapped device registers.
+    ///
+    /// This function enforces alignment of T and will panic if the memory is not properly aligned.
+    ///
+    pub unsafe fn write_volatile<T>(&mut self, index: usize, value: T) {
+        self.check_bounds(index, size_of::<T>());
+
+        let ptr = self.data_ptr.wrapping_add(index);
+        if ptr.align_offset(align_of::<T>()) != 0 {
+            panic!("Index {} is not properly aligned for {}", index, align_of::<T>());
+        }
+
+        ptr.cast::<T>().write_volatile(value)
+    }
+
+    ///
+    /// Reads the byte at the given location using a volatile read.
+    /// panics on out of bounds.
+    ///
+    pub fn read_volatile_u8(&self, index: usize) -> u8 {
+        unsafe { self.read_volatile::<u8>(index) }
+    }
+
+    ///
+    /// Writes the byte to the given location using a volatile write.
+    /// panics on out of bounds.
+    ///
+    pub fn write_volatile_u8(&mut self, index: usize, value: u8) {
+        unsafe { self.write_volatile::<u8>(index, value) }
+    }
+
+    ///
+    /// Reads the u32 at the given location using a volatile read.
+    /// panics on out of bounds or if the location is not aligned to 4.
+    ///
+    pub fn read_volatile_u32(&self, index: usize) -> u32 {
+        unsafe { self.read_volatile::<u32>(index) }
+    }
+
+    ///
+    /// Writes the u32 to the given location using a volatile write.
+    /// panics on out of bounds or if the location is not aligned to 4.
+    ///
+    pub fn write_volatile_u32(&mut self, index: usize, value: u32) {
+        unsafe { self.write_volatile::<u32>(index, value) }
+    }
+
 
     ///
     /// Sets the value at the given location to the value.
@@ -705,68 +1696,141 @@ impl HBuf {
     ///
     pub unsafe fn set<T: Sized>(&mut self, index: usize, value: T) {
         let sz = size_of::<T>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
+        self.check_bounds(index, sz);
+        unsafe { self.data_ptr.wrapping_add(index).cast::<T>().write_unaligned(value); }
+    }
+
+    ///
+    /// Sets the value at the given location to the value.
+    /// The alignment of T and the memory location does not matter as this method uses "write_unaligned"
+    /// to write memory.
+    ///
+    /// returns HBufError::OutOfBounds on out of bounds.
+    ///
+    pub unsafe fn try_set<T: Sized>(&mut self, index: usize, value: T) -> Result<(), HBufError> {
+        if !self.in_bounds(index, size_of::<T>()) {
+            return Err(HBufError::OutOfBounds { index, limit: self.limit });
         }
         unsafe { self.data_ptr.wrapping_add(index).cast::<T>().write_unaligned(value); }
+        Ok(())
     }
 
-    known_type!(i8, as_slice_i8, as_mut_slice_i8, get_i8, set_i8);
-    known_type!(i16, as_slice_i16, as_mut_slice_i16, get_i16, set_i16);
-    known_type!(i32, as_slice_i32, as_mut_slice_i32, get_i32, set_i32);
-    known_type!(i64, as_slice_i64, as_mut_slice_i64, get_i64, set_i64);
-    known_type!(i128, as_slice_i128, as_mut_slice_i128, get_i128, set_i128);
+    known_type!(i8, as_slice_i8, as_mut_slice_i8, get_i8, try_get_i8, get_i8_unchecked, set_i8, try_set_i8, set_i8_unchecked);
+    known_type!(i16, as_slice_i16, as_mut_slice_i16, get_i16, try_get_i16, get_i16_unchecked, set_i16, try_set_i16, set_i16_unchecked);
+    known_type!(i32, as_slice_i32, as_mut_slice_i32, get_i32, try_get_i32, get_i32_unchecked, set_i32, try_set_i32, set_i32_unchecked);
+    known_type!(i64, as_slice_i64, as_mut_slice_i64, get_i64, try_get_i64, get_i64_unchecked, set_i64, try_set_i64, set_i64_unchecked);
+    known_type!(i128, as_slice_i128, as_mut_slice_i128, get_i128, try_get_i128, get_i128_unchecked, set_i128, try_set_i128, set_i128_unchecked);
 
-    known_type!(u8, as_slice_u8, as_mut_slice_u8, get_u8, set_u8);
-    known_type!(u16, as_slice_u16, as_mut_slice_u16, get_u16, set_u16);
-    known_type!(u32, as_slice_u32, as_mut_slice_u32, get_u32, set_u32);
-    known_type!(u64, as_slice_u64, as_mut_slice_u64, get_u64, set_u64);
-    known_type!(u128, as_slice_u128, as_mut_slice_u128, get_u128, set_u128);
+    known_type!(u8, as_slice_u8, as_mut_slice_u8, get_u8, try_get_u8, get_u8_unchecked, set_u8, try_set_u8, set_u8_unchecked);
+    known_type!(u16, as_slice_u16, as_mut_slice_u16, get_u16, try_get_u16, get_u16_unchecked, set_u16, try_set_u16, set_u16_unchecked);
+    known_type!(u32, as_slice_u32, as_mut_slice_u32, get_u32, try_get_u32, get_u32_unchecked, set_u32, try_set_u32, set_u32_unchecked);
+    known_type!(u64, as_slice_u64, as_mut_slice_u64, get_u64, try_get_u64, get_u64_unchecked, set_u64, try_set_u64, set_u64_unchecked);
+    known_type!(u128, as_slice_u128, as_mut_slice_u128, get_u128, try_get_u128, get_u128_unchecked, set_u128, try_set_u128, set_u128_unchecked);
 
-    known_type!(usize, as_slice_usize, as_mut_slice_usize, get_usize, set_usize);
-    known_type!(isize, as_slice_isize, as_mut_slice_isize, get_isize, set_isize);
+    known_type!(usize, as_slice_usize, as_mut_slice_usize, get_usize, try_get_usize, get_usize_unchecked, set_usize, try_set_usize, set_usize_unchecked);
+    known_type!(isize, as_slice_isize, as_mut_slice_isize, get_isize, try_get_isize, get_isize_unchecked, set_isize, try_set_isize, set_isize_unchecked);
 
-    known_type!(f32, as_slice_f32, as_mut_slice_f32, get_f32, set_f32);
-    known_type!(f64, as_slice_f64, as_mut_slice_f64, get_f64, set_f64);
+    known_type!(f32, as_slice_f32, as_mut_slice_f32, get_f32, try_get_f32, get_f32_unchecked, set_f32, try_set_f32, set_f32_unchecked);
+    known_type!(f64, as_slice_f64, as_mut_slice_f64, get_f64, try_get_f64, get_f64_unchecked, set_f64, try_set_f64, set_f64_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u24, as_slice_u24, as_mut_slice_u24, get_u24, set_u24);
+    known_type!(uintx::u24, as_slice_u24, as_mut_slice_u24, get_u24, try_get_u24, get_u24_unchecked, set_u24, try_set_u24, set_u24_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u40, as_slice_u40, as_mut_slice_u40, get_u40, set_u40);
+    known_type!(uintx::u40, as_slice_u40, as_mut_slice_u40, get_u40, try_get_u40, get_u40_unchecked, set_u40, try_set_u40, set_u40_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u48, as_slice_u48, as_mut_slice_u48, get_u48, set_u48);
+    known_type!(uintx::u48, as_slice_u48, as_mut_slice_u48, get_u48, try_get_u48, get_u48_unchecked, set_u48, try_set_u48, set_u48_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u56, as_slice_u56, as_mut_slice_u56, get_u56, set_u56);
+    known_type!(uintx::u56, as_slice_u56, as_mut_slice_u56, get_u56, try_get_u56, get_u56_unchecked, set_u56, try_set_u56, set_u56_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u72, as_slice_u72, as_mut_slice_u72, get_u72, set_u72);
+    known_type!(uintx::u72, as_slice_u72, as_mut_slice_u72, get_u72, try_get_u72, get_u72_unchecked, set_u72, try_set_u72, set_u72_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u80, as_slice_u80, as_mut_slice_u80, get_u80, set_u80);
+    known_type!(uintx::u80, as_slice_u80, as_mut_slice_u80, get_u80, try_get_u80, get_u80_unchecked, set_u80, try_set_u80, set_u80_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u88, as_slice_u88, as_mut_slice_u88, get_u88, set_u88);
+    known_type!(uintx::u88, as_slice_u88, as_mut_slice_u88, get_u88, try_get_u88, get_u88_unchecked, set_u88, try_set_u88, set_u88_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u96, as_slice_u96, as_mut_slice_u96, get_u96, set_u96);
+    known_type!(uintx::u96, as_slice_u96, as_mut_slice_u96, get_u96, try_get_u96, get_u96_unchecked, set_u96, try_set_u96, set_u96_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u104, as_slice_u104, as_mut_slice_u104, get_u104, set_u104);
+    known_type!(uintx::u104, as_slice_u104, as_mut_slice_u104, get_u104, try_get_u104, get_u104_unchecked, set_u104, try_set_u104, set_u104_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u112, as_slice_u112, as_mut_slice_u112, get_u112, set_u112);
+    known_type!(uintx::u112, as_slice_u112, as_mut_slice_u112, get_u112, try_get_u112, get_u112_unchecked, set_u112, try_set_u112, set_u112_unchecked);
 
     #[cfg(feature = "uintx_support")]
-    known_type!(uintx::u120, as_slice_u120, as_mut_slice_u120, get_u120, set_u120);
+    known_type!(uintx::u120, as_slice_u120, as_mut_slice_u120, get_u120, try_get_u120, get_u120_unchecked, set_u120, try_set_u120, set_u120_unchecked);
 
     #[cfg(feature = "f16_support")]
-    known_type!(half::f16, as_slice_f16, as_mut_slice_f16, get_f16, set_f16);
+    known_type!(half::f16, as_slice_f16, as_mut_slice_f16, get_f16, try_get_f16, get_f16_unchecked, set_f16, try_set_f16, set_f16_unchecked);
 
     #[cfg(feature = "f128_support")]
-    known_type!(f128::f128, as_slice_f128, as_mut_slice_f128, get_f128, set_f128);
+    known_type!(f128::f128, as_slice_f128, as_mut_slice_f128, get_f128, try_get_f128, get_f128_unchecked, set_f128, try_set_f128, set_f128_unchecked);
+
+    swap_type!(u16, swap_bytes_u16);
+    swap_type!(u32, swap_bytes_u32);
+    swap_type!(u64, swap_bytes_u64);
+
+    ///
+    /// Reads a byte at the position and advances the position by 1.
+    /// Returns an UnexpectedEof error if the position is at the limit.
+    ///
+    pub fn read_u8(&mut self) -> io::Result<u8> {
+        let mut bytes = [0u8; 1];
+        self.read_exact(&mut bytes)?;
+        Ok(bytes[0])
+    }
+
+    ///
+    /// Reads a signed byte at the position and advances the position by 1.
+    /// Returns an UnexpectedEof error if the position is at the limit.
+    ///
+    pub fn read_i8(&mut self) -> io::Result<i8> {
+        Ok(self.read_u8()? as i8)
+    }
+
+    ///
+    /// Writes a byte at the position and advances the position by 1.
+    /// Returns an UnexpectedEof error if the position is at the limit.
+    ///
+    pub fn write_u8(&mut self, value: u8) -> io::Result<()> {
+        self.write_all(&[value])
+    }
+
+    ///
+    /// Writes a signed byte at the position and advances the position by 1.
+    /// Returns an UnexpectedEof error if the position is at the limit.
+    ///
+    pub fn write_i8(&mut self, value: i8) -> io::Result<()> {
+        self.write_u8(value as u8)
+    }
+
+    stream_type!(u16, read_u16_le, read_u16_be, write_u16_le, write_u16_be);
+    stream_type!(i16, read_i16_le, read_i16_be, write_i16_le, write_i16_be);
+    stream_type!(u32, read_u32_le, read_u32_be, write_u32_le, write_u32_be);
+    stream_type!(i32, read_i32_le, read_i32_be, write_i32_le, write_i32_be);
+    stream_type!(u64, read_u64_le, read_u64_be, write_u64_le, write_u64_be);
+    stream_type!(i64, read_i64_le, read_i64_be, write_i64_le, write_i64_be);
+    stream_type!(u128, read_u128_le, read_u128_be, write_u128_le, write_u128_be);
+    stream_type!(i128, read_i128_le, read_i128_be, write_i128_le, write_i128_be);
+    stream_type!(f32, read_f32_le, read_f32_be, write_f32_le, write_f32_be);
+    stream_type!(f64, read_f64_le, read_f64_be, write_f64_le, write_f64_be);
+
+    endian_type!(u16, get_u16_le, get_u16_be, set_u16_le, set_u16_be);
+    endian_type!(i16, get_i16_le, get_i16_be, set_i16_le, set_i16_be);
+    endian_type!(u32, get_u32_le, get_u32_be, set_u32_le, set_u32_be);
+    endian_type!(i32, get_i32_le, get_i32_be, set_i32_le, set_i32_be);
+    endian_type!(u64, get_u64_le, get_u64_be, set_u64_le, set_u64_be);
+    endian_type!(i64, get_i64_le, get_i64_be, set_i64_le, set_i64_be);
+    endian_type!(u128, get_u128_le, get_u128_be, set_u128_le, set_u128_be);
+    endian_type!(i128, get_i128_le, get_i128_be, set_i128_le, set_i128_be);
+    endian_type!(f32, get_f32_le, get_f32_be, set_f32_le, set_f32_be);
+    endian_type!(f64, get_f64_le, get_f64_be, set_f64_le, set_f64_be);
 
     #[cfg(target_has_atomic = "8")]
     atomic_type!(u8, std::sync::atomic::AtomicU8, as_slice_atomic_u8, as_atomic_u8, load_u8, store_u8, swap_u8, compare_and_exchange_u8, compare_and_exchange_weak_u8);
@@ -786,6 +1850,42 @@ impl HBuf {
     #[cfg(target_has_atomic = "32")]
     atomic_type!(i32, std::sync::atomic::AtomicI32, as_slice_atomic_i32, as_atomic_i32, atomic_load_i32, atomic_store_i32, atomic_swap_i32, atomic_compare_and_exchange_i32, atomic_compare_and_exchange_weak_i32);
 
+    ///
+    /// Memory fence with the given ordering, see std::sync::atomic::fence.
+    ///
+    #[inline]
+    pub fn fence(ordering: Ordering) {
+        std::sync::atomic::fence(ordering);
+    }
+
+    ///
+    /// Publishes all prior (non-atomic) writes to the buffer by storing the value at flag_index with release ordering
+    /// after a release fence. Another thread that observes the value using acquire_u32 is guaranteed to also observe
+    /// all writes that happened before this call.
+    ///
+    /// The flag_index must be aligned to 4 bytes.
+    /// panics on out of bounds.
+    ///
+    #[cfg(target_has_atomic = "32")]
+    #[inline]
+    pub fn publish_u32(&self, flag_index: usize, value: u32) {
+        HBuf::fence(Ordering::Release);
+        self.atomic_store_u32(flag_index, value, Ordering::Release);
+    }
+
+    ///
+    /// Loads the value at flag_index with acquire ordering.
+    /// If the value was stored by publish_u32 then all writes made by the publishing thread before that call are visible.
+    ///
+    /// The flag_index must be aligned to 4 bytes.
+    /// panics on out of bounds.
+    ///
+    #[cfg(target_has_atomic = "32")]
+    #[inline]
+    pub fn acquire_u32(&self, flag_index: usize) -> u32 {
+        self.atomic_load_u32(flag_index, Ordering::Acquire)
+    }
+
     #[cfg(target_has_atomic = "64")]
     atomic_type!(u64, std::sync::atomic::AtomicU64, as_slice_atomic_u64, as_atomic_u64, atomic_load_u64, atomic_store_u64, atomic_swap_u64, atomic_compare_and_exchange_u64, atomic_compare_and_exchange_weak_u64);
 
@@ -798,6 +1898,56 @@ impl HBuf {
     #[cfg(target_has_atomic = "ptr")]
     atomic_type!(isize, std::sync::atomic::AtomicIsize, as_slice_atomic_isize, as_atomic_isize, atomic_load_isize, atomic_store_isize, atomic_swap_isize, atomic_compare_and_exchange_isize, atomic_compare_and_exchange_weak_isize);
 
+    ///
+    /// Copies the bytes up to the limit into dst using one atomic byte load with the given ordering per byte.
+    ///
+    /// This avoids data races with other threads that concurrently modify the buffer using atomic stores,
+    /// but it is not a consistent snapshot of the whole buffer. Each byte holds a value that was stored at some point,
+    /// but different bytes may come from different writes, even bytes that were written by a single wider atomic store.
+    ///
+    /// Returns the amount of bytes copied which is the smaller of limit and dst.len().
+    /// panics if ordering is Release or AcqRel.
+    ///
+    #[cfg(target_has_atomic = "8")]
+    pub fn atomic_copy_to(&self, dst: &mut [u8], ordering: Ordering) -> usize {
+        let len = self.limit.min(dst.len());
+        for (index, byte) in dst[..len].iter_mut().enumerate() {
+            *byte = unsafe { std::sync::atomic::AtomicU8::from_ptr(self.data_ptr.wrapping_add(index)).load(ordering) };
+        }
+
+        len
+    }
+
+    #[cfg(target_has_atomic = "8")]
+    atomic_fetch_type!(u8, std::sync::atomic::AtomicU8, atomic_fetch_add_u8, atomic_fetch_sub_u8, atomic_fetch_and_u8, atomic_fetch_or_u8, atomic_fetch_xor_u8, atomic_fetch_max_u8, atomic_fetch_min_u8, atomic_fetch_update_u8);
+
+    #[cfg(target_has_atomic = "8")]
+    atomic_fetch_type!(i8, std::sync::atomic::AtomicI8, atomic_fetch_add_i8, atomic_fetch_sub_i8, atomic_fetch_and_i8, atomic_fetch_or_i8, atomic_fetch_xor_i8, atomic_fetch_max_i8, atomic_fetch_min_i8, atomic_fetch_update_i8);
+
+    #[cfg(target_has_atomic = "16")]
+    atomic_fetch_type!(u16, std::sync::atomic::AtomicU16, atomic_fetch_add_u16, atomic_fetch_sub_u16, atomic_fetch_and_u16, atomic_fetch_or_u16, atomic_fetch_xor_u16, atomic_fetch_max_u16, atomic_fetch_min_u16, atomic_fetch_update_u16);
+
+    #[cfg(target_has_atomic = "16")]
+    atomic_fetch_type!(i16, std::sync::atomic::AtomicI16, atomic_fetch_add_i16, atomic_fetch_sub_i16, atomic_fetch_and_i16, atomic_fetch_or_i16, atomic_fetch_xor_i16, atomic_fetch_max_i16, atomic_fetch_min_i16, atomic_fetch_update_i16);
+
+    #[cfg(target_has_atomic = "32")]
+    atomic_fetch_type!(u32, std::sync::atomic::AtomicU32, atomic_fetch_add_u32, atomic_fetch_sub_u32, atomic_fetch_and_u32, atomic_fetch_or_u32, atomic_fetch_xor_u32, atomic_fetch_max_u32, atomic_fetch_min_u32, atomic_fetch_update_u32);
+
+    #[cfg(target_has_atomic = "32")]
+    atomic_fetch_type!(i32, std::sync::atomic::AtomicI32, atomic_fetch_add_i32, atomic_fetch_sub_i32, atomic_fetch_and_i32, atomic_fetch_or_i32, atomic_fetch_xor_i32, atomic_fetch_max_i32, atomic_fetch_min_i32, atomic_fetch_update_i32);
+
+    #[cfg(target_has_atomic = "64")]
+    atomic_fetch_type!(u64, std::sync::atomic::AtomicU64, atomic_fetch_add_u64, atomic_fetch_sub_u64, atomic_fetch_and_u64, atomic_fetch_or_u64, atomic_fetch_xor_u64, atomic_fetch_max_u64, atomic_fetch_min_u64, atomic_fetch_update_u64);
+
+    #[cfg(target_has_atomic = "64")]
+    atomic_fetch_type!(i64, std::sync::atomic::AtomicI64, atomic_fetch_add_i64, atomic_fetch_sub_i64, atomic_fetch_and_i64, atomic_fetch_or_i64, atomic_fetch_xor_i64, atomic_fetch_max_i64, atomic_fetch_min_i64, atomic_fetch_update_i64);
+
+    #[cfg(target_has_atomic = "ptr")]
+    atomic_fetch_type!(usize, std::sync::atomic::AtomicUsize, atomic_fetch_add_usize, atomic_fetch_sub_usize, atomic_fetch_and_usize, atomic_fetch_or_usize, atomic_fetch_xor_usize, atomic_fetch_max_usize, atomic_fetch_min_usize, atomic_fetch_update_usize);
+
+    #[cfg(target_has_atomic = "ptr")]
+    atomic_fetch_type!(isize, std::sync::atomic::AtomicIsize, atomic_fetch_add_isize, atomic_fetch_sub_isize, atomic_fetch_and_isize, atomic_fetch_or_isize, atomic_fetch_xor_isize, atomic_fetch_max_isize, atomic_fetch_min_isize, atomic_fetch_update_isize);
+
      ///
     /// Returns a slice of Atomic "references" to the buffer.
     /// The "references" remain valid even if the buffer limit changes.
@@ -829,7 +1979,7 @@ impl HBuf {
     #[inline]
     pub fn as_atomic_ptr<T>(&self, index: usize) -> Option<&AtomicPtr<T>> {
         let sz = size_of::<AtomicPtr<T>>();
-        if index+sz-1 >= self.limit {
+        if !self.in_bounds(index, sz) {
             return None;
         }
         let ptr = self.data_ptr.wrapping_add(index);
@@ -848,9 +1998,7 @@ impl HBuf {
     #[inline]
     pub fn atomic_load_ptr<T>(&self, index: usize, ordering: Ordering) -> *mut T {
         let sz = size_of::<AtomicPtr<T>>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-        }
+        self.check_bounds(index, sz);
         let ptr = self.data_ptr.wrapping_add(index);
         debug_assert_eq!(ptr.align_offset(align_of::<AtomicPtr<T>>()), 0);
         unsafe {
@@ -865,9 +2013,7 @@ impl HBuf {
     #[inline]
     pub fn atomic_store_ptr<T>(&self, index: usize, value: *mut T, ordering: Ordering) {
         let sz = size_of::<AtomicPtr<T>>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-        }
+        self.check_bounds(index, sz);
         let ptr = self.data_ptr.wrapping_add(index);
         debug_assert_eq!(ptr.align_offset(align_of::<AtomicPtr<T>>()), 0);
         unsafe {
@@ -882,9 +2028,7 @@ impl HBuf {
     #[inline]
     pub fn atomic_swap_ptr<T>(&self, index: usize, value: *mut T, ordering: Ordering) -> *mut T {
         let sz = size_of::<AtomicPtr<T>>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-        }
+        self.check_bounds(index, sz);
         let ptr = self.data_ptr.wrapping_add(index);
         debug_assert_eq!(ptr.align_offset(align_of::<AtomicPtr<T>>()), 0);
         unsafe {
@@ -899,9 +2043,7 @@ impl HBuf {
     #[inline]
     pub fn atomic_compare_exchange_ptr<T>(&self, index: usize, current: *mut T, update: *mut T, success_ordering: Ordering, failure_ordering: Ordering) -> Result<*mut T, *mut T> {
         let sz = size_of::<AtomicPtr<T>>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-        }
+        self.check_bounds(index, sz);
         let ptr = self.data_ptr.wrapping_add(index);
         debug_assert_eq!(ptr.align_offset(align_of::<AtomicPtr<T>>()), 0);
         unsafe {
@@ -916,9 +2058,7 @@ impl HBuf {
     #[inline]
     pub fn atomic_compare_exchange_weak_ptr<T>(&self, index: usize, current: *mut T, update: *mut T, success_ordering: Ordering, failure_ordering: Ordering) -> Result<*mut T, *mut T> {
         let sz = size_of::<AtomicPtr<T>>();
-        if index+sz-1 >= self.limit {
-            panic!("Index {} is out of bounds for HBuf with limit {}", index+sz-1, self.limit);
-        }
+        self.check_bounds(index, sz);
         let ptr = self.data_ptr.wrapping_add(index);
         debug_assert_eq!(ptr.align_offset(align_of::<AtomicPtr<T>>()), 0);
         unsafe {
@@ -943,6 +2083,10 @@ impl HBuf {
         if self.position > self.limit {
             self.position = self.limit;
         }
+
+        if self.mark.is_some_and(|mark| mark > self.limit) {
+            self.mark = None;
+        }
     }
 
     ///
@@ -962,9 +2106,87 @@ impl HBuf {
             self.position = self.limit;
         }
 
+        if self.mark.is_some_and(|mark| mark > self.limit) {
+            self.mark = None;
+        }
+
         true
     }
 
+    ///
+    /// Changes the limit of accessible bytes in the buffer to desired or to the capacity if desired > capacity.
+    /// This has no effect on slices creates prior to calling this method.
+    ///
+    /// returns the limit that was set.
+    ///
+    pub fn set_limit_clamped(&mut self, desired: usize) -> usize {
+        self.set_limit(desired.min(self.capacity));
+        self.limit
+    }
+
+    ///
+    /// Attaches a shared atomic limit to this HBuf that is initialized with the current limit.
+    /// All clones created after calling this method share the same atomic limit,
+    /// which allows threads to coordinate a moving boundary within a single allocation
+    /// by using atomic_set_limit and atomic_limit.
+    /// Buffers created by split or try_split never share the atomic limit.
+    ///
+    /// Calling this method on a HBuf that already has an atomic limit is a noop.
+    ///
+    pub fn enable_atomic_limit(&mut self) {
+        if self.shared_limit.is_none() {
+            self.shared_limit = Some(Arc::new(AtomicUsize::new(self.limit)));
+        }
+    }
+
+    ///
+    /// Returns true if this HBuf has a shared atomic limit.
+    ///
+    pub fn has_atomic_limit(&self) -> bool {
+        self.shared_limit.is_some()
+    }
+
+    ///
+    /// Stores a new value in the shared atomic limit.
+    /// This does not change the limit of this HBuf or any of its clones,
+    /// each holder of the HBuf must call sync_limit to adopt the new value.
+    ///
+    /// panics if limit > capacity or if this HBuf has no atomic limit.
+    ///
+    pub fn atomic_set_limit(&self, new_limit: usize, ordering: Ordering) {
+        if new_limit > self.capacity {
+            panic!("Limit {} is out of bounds for HBuf with capacity {}", new_limit, self.capacity);
+        }
+
+        match self.shared_limit.as_ref() {
+            Some(shared) => shared.store(new_limit, ordering),
+            None => panic!("HBuf has no atomic limit, call enable_atomic_limit first")
+        }
+    }
+
+    ///
+    /// Loads the shared atomic limit.
+    /// Returns the regular limit if this HBuf has no atomic limit.
+    ///
+    pub fn atomic_limit(&self, ordering: Ordering) -> usize {
+        match self.shared_limit.as_ref() {
+            Some(shared) => shared.load(ordering),
+            None => self.limit
+        }
+    }
+
+    ///
+    /// Loads the shared atomic limit and sets it as the limit of this HBuf, behaves like set_limit otherwise.
+    /// This is a noop if this HBuf has no atomic limit.
+    ///
+    /// returns the new limit.
+    ///
+    pub fn sync_limit(&mut self, ordering: Ordering) -> usize {
+        let new_limit = self.atomic_limit(ordering);
+        self.set_limit(new_limit);
+        new_limit
+    }
+
     ///
     /// Changes the position. (Relevant for Seek trait)
     ///
@@ -992,67 +2214,1205 @@ impl HBuf {
 
     ///
     /// Flips the HeapBuf.
-    /// It sets the limit ot the previous position and sets the position to 0.
+    /// It sets the limit ot the previous position, sets the position to 0 and discards the mark.
     ///
     /// This is useful when transitioning a buffer from reading to writing and vice versa.
     ///
     pub fn flip(&mut self) {
         self.limit = self.position;
         self.position = 0;
+        self.mark = None;
+    }
+
+    ///
+    /// Reduces the limit to new_limit if it is smaller than the current limit.
+    /// Unlike set_limit this never increases the limit and therefore never panics.
+    /// The position and mark are adjusted like set_limit does.
+    ///
+    pub fn truncate(&mut self, new_limit: usize) {
+        if new_limit < self.limit {
+            self.set_limit(new_limit);
+        }
+    }
+
+    ///
+    /// Sets limit and position to 0 so the buffer can be reused. The memory is not deallocated.
+    /// This also discards the mark.
+    ///
+    pub fn clear(&mut self) {
+        self.limit = 0;
+        self.position = 0;
+        self.mark = None;
     }
 
     ///
     /// Resets position and limit.
+    /// This also discards the mark.
     ///
     pub fn reset(&mut self) {
         self.limit = self.capacity;
         self.position = 0;
+        self.mark = None;
     }
 
     ///
-    /// Splits off a "sub" buffer that is backed by the same memory as this HeapBuf.
-    /// The sub buffer may be smaller than the current capacity or start at a given offset.
-    /// This function leaves this HeapBuf unmodified.
+    /// Remembers the current position so that it can later be restored by calling reset_to_mark.
+    /// The mark is discarded by flip, reset, compact and by reducing the limit below the mark.
     ///
-    /// The limit of the sub buffer is set to its capacity and the position is always initialized with 0.
-    /// panics if off+length > capacity.
+    pub fn mark(&mut self) {
+        self.mark = Some(self.position);
+    }
+
     ///
-    pub fn split(&self, off: usize, length: usize) -> HBuf {
-        if off+length > self.capacity {
-            panic!("Cannot split of a HBuf with {} bytes at offset {} because the capacity of the source buffer is only {}", length, off, self.capacity);
+    /// Restores the position that was previously remembered by calling mark.
+    /// The mark remains set so that it can be restored multiple times.
+    ///
+    /// returns an error if no mark is set.
+    ///
+    pub fn reset_to_mark(&mut self) -> io::Result<()> {
+        match self.mark {
+            Some(mark) => {
+                self.position = mark;
+                Ok(())
+            }
+            None => Err(Error::new(ErrorKind::InvalidInput, "mark is not set"))
         }
+    }
 
-        HBuf {
-            data_ptr: unsafe {self.data_ptr.wrapping_add(off).as_sync_mut()},
-            capacity: length,
-            limit: length,
-            position: 0,
-            destructor: self.destructor.clone(),
+    ///
+    /// Returns the current position and limit so that they can later be restored by calling restore_state.
+    /// See restore_state for an example.
+    ///
+    pub fn save_state(&self) -> BufState {
+        BufState {
+            position: self.position,
+            limit: self.limit
         }
     }
 
     ///
-    /// Splits off a "sub" buffer that is backed by the same memory as this HeapBuf.
-    /// The sub buffer may be smaller than the current capacity or start at a given offset.
-    /// This function leaves this HeapBuf unmodified.
+    /// Restores the position and limit that were previously returned by save_state.
+    /// The mark is discarded if it is beyond the restored limit.
     ///
-    /// The limit of the sub buffer is set to its capacity and the position is always initialized with 0.
-    /// panics if off+length > capacity.
+    /// panics if the saved limit > capacity or the saved position > saved limit.
+    /// This can happen if the HBuf was resized after the state was saved.
     ///
-    pub fn try_split(&self, off: usize, length: usize) -> Option<HBuf> {
-        if off+length > self.capacity {
-            return None;
+    /// ```
+    /// use heapbuf::HBuf;
+    ///
+    /// let mut buf = HBuf::allocate_zeroed(32);
+    /// buf.set_limit(20);
+    /// buf.set_position(4);
+    /// let state = buf.save_state();
+    ///
+    /// buf.set_limit(32);
+    /// buf.set_position(30);
+    /// buf.restore_state(state);
+    /// assert_eq!(buf.position(), 4);
+    /// assert_eq!(buf.limit(), 20);
+    /// ```
+    ///
+    pub fn restore_state(&mut self, state: BufState) {
+        if state.limit > self.capacity || state.position > state.limit {
+            panic!("State with position {} and limit {} is out of bounds for HBuf with capacity {}", state.position, state.limit, self.capacity);
         }
 
-        Some(HBuf {
-            data_ptr: unsafe {self.data_ptr.wrapping_add(off).as_sync_mut()},
+        self.set_limit(state.limit);
+        self.position = state.position;
+    }
+
+    ///
+    /// Compacts the HeapBuf.
+    /// It moves the remaining bytes between position and limit to the start of the buffer,
+    /// sets the position to the amount of bytes moved and sets the limit to the capacity.
+    ///
+    /// This is useful when more data should be appended after partially reading the buffer.
+    ///
+    pub fn compact(&mut self) {
+        let remaining = self.limit - self.position;
+        unsafe { std::ptr::copy(self.data_ptr.wrapping_add(self.position), self.data_ptr.inner(), remaining) }
+        self.position = remaining;
+        self.limit = self.capacity;
+        self.mark = None;
+    }
+
+    ///
+    /// Splits off a "sub" buffer that is backed by the same memory as this HeapBuf.
+    /// The sub buffer may be smaller than the current capacity or start at a given offset.
+    /// This function leaves this HeapBuf unmodified.
+    ///
+    /// The limit of the sub buffer is set to its capacity and the position is always initialized with 0.
+    /// panics if off+length > capacity or if off+length overflows.
+    ///
+    pub fn split(&self, off: usize, length: usize) -> HBuf {
+        if !off.checked_add(length).is_some_and(|end| end <= self.capacity) {
+            panic!("Cannot split of a HBuf with {} bytes at offset {} because the capacity of the source buffer is only {}", length, off, self.capacity);
+        }
+
+        HBuf {
+            data_ptr: unsafe {self.data_ptr.wrapping_add(off).as_sync_mut()},
             capacity: length,
             limit: length,
             position: 0,
+            mark: None,
             destructor: self.destructor.clone(),
+            shared_limit: None,
+        }
+    }
+
+    ///
+    /// Returns a new handle that is backed by the same memory as this HeapBuf.
+    /// Unlike clone the new handle does not copy the position, limit or mark of this HeapBuf.
+    /// This function leaves this HeapBuf unmodified.
+    ///
+    /// The limit of the new handle is set to the capacity and the position is always initialized with 0.
+    ///
+    pub fn duplicate(&self) -> HBuf {
+        HBuf {
+            data_ptr: self.data_ptr,
+            capacity: self.capacity,
+            limit: self.capacity,
+            position: 0,
+            mark: None,
+            destructor: self.destructor.clone(),
+            shared_limit: None,
+        }
+    }
+
+    ///
+    /// Splits this HeapBuf into two "sub" buffers that are backed by the same memory as this HeapBuf.
+    /// The first covers [0..mid] and the second covers [mid..capacity].
+    /// This function leaves this HeapBuf unmodified.
+    ///
+    /// The limit of the sub buffers is set to their capacity and the position is always initialized with 0.
+    /// panics if mid > capacity.
+    ///
+    pub fn split_at(&self, mid: usize) -> (HBuf, HBuf) {
+        if mid > self.capacity {
+            panic!("Cannot split a HBuf at {} because the capacity of the source buffer is only {}", mid, self.capacity);
+        }
+
+        (self.split(0, mid), self.split(mid, self.capacity - mid))
+    }
+
+    ///
+    /// Returns an iterator over "sub" buffers of the given size that are backed by the same memory as this HeapBuf.
+    /// The sub buffers cover [0..chunk_size], [chunk_size..2*chunk_size] and so on up to the capacity.
+    /// The last sub buffer is smaller if the capacity is not a multiple of the chunk size.
+    /// This function leaves this HeapBuf unmodified.
+    ///
+    /// The limit of the sub buffers is set to their capacity and the position is always initialized with 0.
+    /// panics if chunk_size is 0.
+    ///
+    pub fn split_chunks(&self, chunk_size: usize) -> impl Iterator<Item = HBuf> {
+        if chunk_size == 0 {
+            panic!("chunk_size is 0");
+        }
+
+        let source = self.clone();
+        let capacity = self.capacity;
+        (0..capacity).step_by(chunk_size).map(move |off| HBuf::split(&source, off, chunk_size.min(capacity - off)))
+    }
+
+    ///
+    /// Splits off a "sub" buffer that is backed by the same memory as this HeapBuf.
+    /// The sub buffer may be smaller than the current capacity or start at a given offset.
+    /// This function leaves this HeapBuf unmodified.
+    ///
+    /// The limit of the sub buffer is set to its capacity and the position is always initialized with 0.
+    /// returns None if off+length > capacity or if off+length overflows.
+    ///
+    pub fn try_split(&self, off: usize, length: usize) -> Option<HBuf> {
+        if !off.checked_add(length).is_some_and(|end| end <= self.capacity) {
+            return None;
+        }
+
+        Some(HBuf {
+            data_ptr: unsafe {self.data_ptr.wrapping_add(off).as_sync_mut()},
+            capacity: length,
+            limit: length,
+            position: 0,
+            mark: None,
+            destructor: self.destructor.clone(),
+            shared_limit: None,
         })
     }
 
+    ///
+    /// Splits off a "sub" buffer like try_split whose start is aligned to the given alignment.
+    /// This is useful to guarantee that the sub buffer can be used with as_slice_u32 and the like.
+    /// This function leaves this HeapBuf unmodified.
+    ///
+    /// The limit of the sub buffer is set to its capacity and the position is always initialized with 0.
+    /// returns None if off+length > capacity, if off+length overflows, if align is not a power of two
+    /// or if the start of the sub buffer is not aligned to align.
+    ///
+    pub fn split_aligned(&self, off: usize, length: usize, align: usize) -> Option<HBuf> {
+        if !align.is_power_of_two() || off > self.capacity || self.data_ptr.wrapping_add(off).align_offset(align) != 0 {
+            return None;
+        }
+
+        self.try_split(off, length)
+    }
+
+    ///
+    /// Copies bytes starting at the given index into dst.
+    /// This is similar to the posix "pread" function and does not use or modify the position.
+    ///
+    /// Returns the amount of bytes copied which may be less than dst.len() if the limit is reached.
+    /// Returns an UnexpectedEof error if index > limit.
+    ///
+    pub fn read_at(&self, index: usize, dst: &mut [u8]) -> io::Result<usize> {
+        if index > self.limit {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "out of bounds"));
+        }
+
+        let to_copy = dst.len().min(self.limit-index);
+        if to_copy == 0 {
+            return Ok(0);
+        }
+
+        unsafe { std::ptr::copy(self.data_ptr.wrapping_add(index), dst.as_mut_ptr(), to_copy) }
+        Ok(to_copy)
+    }
+
+    ///
+    /// Copies bytes from src into the HBuf starting at the given index.
+    /// This is similar to the posix "pwrite" function and does not use or modify the position.
+    ///
+    /// Returns the amount of bytes copied which may be less than src.len() if the limit is reached.
+    /// Returns an UnexpectedEof error if index > limit.
+    ///
+    pub fn write_at(&mut self, index: usize, src: &[u8]) -> io::Result<usize> {
+        if index > self.limit {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "out of bounds"));
+        }
+
+        let to_copy = src.len().min(self.limit-index);
+        if to_copy == 0 {
+            return Ok(0);
+        }
+
+        unsafe { std::ptr::copy(src.as_ptr(), self.data_ptr.wrapping_add(index), to_copy) }
+        Ok(to_copy)
+    }
+
+    ///
+    /// Fills dst with the bytes starting at the given index.
+    /// This is the positioned equivalent of Read::read_exact and does not use or modify the position.
+    ///
+    /// Returns an UnexpectedEof error and copies nothing if index+dst.len() > limit.
+    ///
+    pub fn read_exact_at(&self, index: usize, dst: &mut [u8]) -> io::Result<()> {
+        if !self.in_bounds(index, dst.len()) {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "failed to fill entire buffer"));
+        }
+
+        unsafe { std::ptr::copy(self.data_ptr.wrapping_add(index), dst.as_mut_ptr(), dst.len()) }
+        Ok(())
+    }
+
+    ///
+    /// Copies all bytes of src into the HBuf starting at the given index.
+    /// This is the positioned equivalent of Write::write_all and does not use or modify the position.
+    ///
+    /// Returns an UnexpectedEof error and copies nothing if index+src.len() > limit.
+    ///
+    pub fn write_all_at(&mut self, index: usize, src: &[u8]) -> io::Result<()> {
+        if !self.in_bounds(index, src.len()) {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "failed write entire buffer"));
+        }
+
+        unsafe { std::ptr::copy(src.as_ptr(), self.data_ptr.wrapping_add(index), src.len()) }
+        Ok(())
+    }
+
+    ///
+    /// Copies all bytes from src into the HBuf starting at the given offset.
+    /// This function does not use or modify the position.
+    ///
+    /// panics if offset+src.len() > limit.
+    ///
+    pub fn copy_from_slice(&mut self, offset: usize, src: &[u8]) {
+        self.check_bounds(offset, src.len());
+        unsafe { std::ptr::copy_nonoverlapping(src.as_ptr(), self.data_ptr.wrapping_add(offset), src.len()) }
+    }
+
+    ///
+    /// Fills dst with the bytes of the HBuf starting at the given offset.
+    /// This function does not use or modify the position.
+    ///
+    /// panics if offset+dst.len() > limit.
+    ///
+    pub fn copy_to_slice(&self, offset: usize, dst: &mut [u8]) {
+        self.check_bounds(offset, dst.len());
+        unsafe { std::ptr::copy_nonoverlapping(self.data_ptr.wrapping_add(offset), dst.as_mut_ptr(), dst.len()) }
+    }
+
+    ///
+    /// Copies the bytes in src_range to dest within the same HBuf.
+    /// This behaves like slice::copy_within and the two regions may overlap.
+    ///
+    /// panics if either the source range or the destination does not lie within the limit.
+    ///
+    pub fn copy_within(&mut self, src_range: Range<usize>, dest: usize) {
+        let (start, end) = self.check_range(src_range);
+        self.check_bounds(dest, end-start);
+        unsafe { std::ptr::copy(self.data_ptr.wrapping_add(start), self.data_ptr.wrapping_add(dest), end-start) }
+    }
+
+    ///
+    /// Sets all bytes up to the limit to the given value.
+    /// This uses std::ptr::write_bytes which is usually lowered to memset.
+    ///
+    pub fn fill(&mut self, value: u8) {
+        unsafe { std::ptr::write_bytes(self.data_ptr.inner(), value, self.limit) }
+    }
+
+    ///
+    /// Sets each byte up to the limit to the value returned by the closure for the index of the byte.
+    /// The closure is called in ascending order of the index.
+    ///
+    pub fn fill_with(&mut self, mut f: impl FnMut(usize) -> u8) {
+        for (index, byte) in self.as_mut_slice().iter_mut().enumerate() {
+            *byte = f(index);
+        }
+    }
+
+    ///
+    /// Sets all bytes in the range to the given value.
+    ///
+    /// panics if the range does not lie within the limit.
+    ///
+    pub fn fill_range(&mut self, range: Range<usize>, value: u8) {
+        let (start, end) = self.check_range(range);
+        unsafe { std::ptr::write_bytes(self.data_ptr.wrapping_add(start), value, end-start) }
+    }
+
+    ///
+    /// Repeatedly writes the pattern starting at offset until the limit is reached.
+    /// The last repetition is cut short if the remaining bytes are not a multiple of the pattern length.
+    ///
+    /// panics if offset > limit or if the pattern is empty.
+    ///
+    pub fn fill_pattern(&mut self, offset: usize, pattern: &[u8]) {
+        if offset > self.limit {
+            panic!("Offset {} is out of bounds for HBuf with limit {}", offset, self.limit);
+        }
+
+        if pattern.is_empty() {
+            panic!("Cannot fill HBuf with an empty pattern");
+        }
+
+        let total = self.limit - offset;
+        let start = self.data_ptr.wrapping_add(offset);
+        let mut filled = pattern.len().min(total);
+        unsafe {
+            std::ptr::copy_nonoverlapping(pattern.as_ptr(), start, filled);
+            //Double the already filled region until the end is reached.
+            while filled < total {
+                let to_copy = filled.min(total - filled);
+                std::ptr::copy_nonoverlapping(start, start.add(filled), to_copy);
+                filled += to_copy;
+            }
+        }
+    }
+
+    ///
+    /// Compares the bytes up to the limit of both HBufs in constant time.
+    /// The time taken only depends on the length and not on the contents of the buffers.
+    ///
+    /// Returns false immediately if the limits differ.
+    ///
+    pub fn ct_eq(&self, other: &HBuf) -> bool {
+        self.ct_eq_slice(other.as_slice())
+    }
+
+    ///
+    /// Compares the bytes up to the limit with the slice in constant time.
+    /// The time taken only depends on the length and not on the contents of the buffer or slice.
+    ///
+    /// Returns false immediately if the length of the slice differs from the limit.
+    ///
+    pub fn ct_eq_slice(&self, other: &[u8]) -> bool {
+        if self.limit != other.len() {
+            return false;
+        }
+
+        let mut diff = 0u8;
+        for (a, b) in self.as_slice().iter().zip(other.iter()) {
+            diff |= std::hint::black_box(a ^ b);
+        }
+
+        std::hint::black_box(diff) == 0
+    }
+
+    ///
+    /// Returns the index of the first occurrence of the byte between position and limit.
+    /// The returned index is relative to the start of the HBuf and not to the position.
+    ///
+    pub fn find_byte(&self, needle: u8) -> Option<usize> {
+        let haystack = &self.as_slice()[self.position..];
+
+        #[cfg(feature = "memchr_support")]
+        let found = memchr::memchr(needle, haystack);
+        #[cfg(not(feature = "memchr_support"))]
+        let found = haystack.iter().position(|b| *b == needle);
+
+        found.map(|idx| idx + self.position)
+    }
+
+    ///
+    /// Returns the index of the last occurrence of the byte between position and limit.
+    /// The returned index is relative to the start of the HBuf and not to the position.
+    ///
+    pub fn rfind_byte(&self, needle: u8) -> Option<usize> {
+        let haystack = &self.as_slice()[self.position..];
+
+        #[cfg(feature = "memchr_support")]
+        let found = memchr::memrchr(needle, haystack);
+        #[cfg(not(feature = "memchr_support"))]
+        let found = haystack.iter().rposition(|b| *b == needle);
+
+        found.map(|idx| idx + self.position)
+    }
+
+    ///
+    /// Returns the index of the first occurrence of the needle between position and limit.
+    /// The returned index is relative to the start of the HBuf and not to the position.
+    /// An empty needle is always found at the position.
+    ///
+    pub fn find_subslice(&self, needle: &[u8]) -> Option<usize> {
+        HBuf::find_subslice_in(&self.as_slice()[self.position..], needle).map(|idx| idx + self.position)
+    }
+
+    fn find_subslice_in(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        #[cfg(feature = "memchr_support")]
+        let found = memchr::memmem::find(haystack, needle);
+        #[cfg(not(feature = "memchr_support"))]
+        let found = if needle.is_empty() {
+            Some(0)
+        } else {
+            haystack.windows(needle.len()).position(|window| window == needle)
+        };
+
+        found
+    }
+
+    ///
+    /// Returns true if the bytes up to the limit start with the prefix.
+    /// This ignores the position.
+    ///
+    pub fn starts_with(&self, prefix: &[u8]) -> bool {
+        self.as_slice().starts_with(prefix)
+    }
+
+    ///
+    /// Returns true if the bytes up to the limit end with the suffix.
+    /// This ignores the position.
+    ///
+    pub fn ends_with(&self, suffix: &[u8]) -> bool {
+        self.as_slice().ends_with(suffix)
+    }
+
+    ///
+    /// Returns true if the needle occurs anywhere in the bytes up to the limit.
+    /// This ignores the position, use find_subslice to search from the position.
+    /// An empty needle is always contained.
+    ///
+    pub fn contains(&self, needle: &[u8]) -> bool {
+        HBuf::find_subslice_in(self.as_slice(), needle).is_some()
+    }
+
+    ///
+    /// Returns a slice of bools that is backed by the HBuf, one bool per byte up to the limit.
+    /// For flags packed into single bits use get_bit/set_bit instead.
+    ///
+    /// panics if any byte is neither 0 nor 1, since such a byte is not a valid bool.
+    ///
+    pub fn as_slice_bool(&self) -> &[bool] {
+        self.check_bool_bytes();
+        unsafe { std::slice::from_raw_parts(self.data_ptr.inner().cast::<bool>(), self.limit) }
+    }
+
+    ///
+    /// Returns a mutable slice of bools that is backed by the HBuf, one bool per byte up to the limit.
+    ///
+    /// panics if any byte is neither 0 nor 1, since such a byte is not a valid bool.
+    ///
+    pub fn as_mut_slice_bool(&mut self) -> &mut [bool] {
+        self.check_bool_bytes();
+        unsafe { std::slice::from_raw_parts_mut(self.data_ptr.inner().cast::<bool>(), self.limit) }
+    }
+
+    fn check_bool_bytes(&self) {
+        if let Some(index) = self.as_slice().iter().position(|value| *value > 1) {
+            panic!("Byte {} at index {} is not a valid bool", self[index], index);
+        }
+    }
+
+    ///
+    /// Returns true if the byte at the given index is not 0.
+    ///
+    /// panics on out of bounds.
+    ///
+    pub fn get_bool(&self, index: usize) -> bool {
+        self.get_u8(index) != 0
+    }
+
+    ///
+    /// Sets the byte at the given index to 1 for true and 0 for false.
+    ///
+    /// panics on out of bounds.
+    ///
+    pub fn set_bool(&mut self, index: usize, value: bool) {
+        self.set_u8(index, value as u8);
+    }
+
+    ///
+    /// Returns the bit at the given bit index.
+    /// Bits are addressed MSB first, bit index 0 is the most significant bit (0x80) of byte 0,
+    /// bit index 7 is the least significant bit (0x01) of byte 0 and bit index 8 is the most significant bit of byte 1.
+    ///
+    /// panics if bit_index >= limit*8
+    ///
+    pub fn get_bit(&self, bit_index: usize) -> bool {
+        let index = bit_index / 8;
+        if index >= self.limit {
+            panic!("Bit index {} is out of bounds for HBuf with limit {}", bit_index, self.limit);
+        }
+
+        let mask = 0x80u8 >> (bit_index % 8);
+        unsafe { *self.data_ptr.wrapping_add(index) & mask != 0 }
+    }
+
+    ///
+    /// Sets the bit at the given bit index.
+    /// Bits are addressed MSB first, see get_bit.
+    ///
+    /// panics if bit_index >= limit*8
+    ///
+    pub fn set_bit(&mut self, bit_index: usize, value: bool) {
+        let index = bit_index / 8;
+        if index >= self.limit {
+            panic!("Bit index {} is out of bounds for HBuf with limit {}", bit_index, self.limit);
+        }
+
+        let mask = 0x80u8 >> (bit_index % 8);
+        unsafe {
+            let ptr = self.data_ptr.wrapping_add(index);
+            if value {
+                *ptr |= mask;
+            } else {
+                *ptr &= !mask;
+            }
+        }
+    }
+
+    ///
+    /// Inverts the bit at the given bit index.
+    /// Bits are addressed MSB first, see get_bit.
+    ///
+    /// panics if bit_index >= limit*8
+    ///
+    pub fn toggle_bit(&mut self, bit_index: usize) {
+        let index = bit_index / 8;
+        if index >= self.limit {
+            panic!("Bit index {} is out of bounds for HBuf with limit {}", bit_index, self.limit);
+        }
+
+        let mask = 0x80u8 >> (bit_index % 8);
+        unsafe { *self.data_ptr.wrapping_add(index) ^= mask; }
+    }
+
+    ///
+    /// Returns the amount of bits that are set in all bytes up to the limit.
+    ///
+    pub fn count_ones(&self) -> u64 {
+        let chunks = self.as_slice().chunks_exact(size_of::<u64>());
+        let rest: u64 = chunks.remainder().iter().map(|b| b.count_ones() as u64).sum();
+        chunks.map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()).count_ones() as u64).sum::<u64>() + rest
+    }
+
+    ///
+    /// Returns an iterator over chunks of the given size of the bytes up to the limit.
+    /// The last chunk is smaller if the limit is not a multiple of the size.
+    /// panics if size is 0.
+    ///
+    pub fn chunks(&self, size: usize) -> std::slice::Chunks<'_, u8> {
+        self.as_slice().chunks(size)
+    }
+
+    ///
+    /// Returns an iterator over chunks of exactly the given size of the bytes up to the limit.
+    /// The remaining bytes that do not fill a chunk can be retrieved with remainder on the iterator.
+    /// panics if size is 0.
+    ///
+    pub fn chunks_exact(&self, size: usize) -> std::slice::ChunksExact<'_, u8> {
+        self.as_slice().chunks_exact(size)
+    }
+
+    ///
+    /// Returns an iterator over mutable chunks of the given size of the bytes up to the limit.
+    /// The last chunk is smaller if the limit is not a multiple of the size.
+    /// panics if size is 0.
+    ///
+    pub fn chunks_mut(&mut self, size: usize) -> std::slice::ChunksMut<'_, u8> {
+        self.as_mut_slice().chunks_mut(size)
+    }
+
+    ///
+    /// Returns an iterator over mutable chunks of exactly the given size of the bytes up to the limit.
+    /// The remaining bytes that do not fill a chunk can be retrieved with into_remainder on the iterator.
+    /// panics if size is 0.
+    ///
+    pub fn chunks_exact_mut(&mut self, size: usize) -> std::slice::ChunksExactMut<'_, u8> {
+        self.as_mut_slice().chunks_exact_mut(size)
+    }
+
+    ///
+    /// Allocates a new HBuf that contains the bytes up to the limit of all given buffers in order.
+    /// The capacity of the new HBuf is the sum of all limits.
+    /// If the sum of all limits is 0 then HBuf::empty is returned.
+    ///
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn concat(bufs: &[&HBuf]) -> HBuf {
+        let size = bufs.iter().fold(0usize, |size, buf| size.checked_add(buf.limit).expect("size overflows"));
+        if size == 0 {
+            return HBuf::empty();
+        }
+
+        let result = HBuf::allocate(size);
+        let mut offset = 0;
+        for buf in bufs {
+            unsafe { std::ptr::copy_nonoverlapping(buf.data_ptr.inner(), result.data_ptr.add(offset), buf.limit) }
+            offset += buf.limit;
+        }
+
+        result
+    }
+
+    ///
+    /// Copies the bytes up to the limit of the other buffer to the end of this HBuf and increases the limit accordingly.
+    /// If the capacity of this HBuf is too small then the memory is resized to fit exactly.
+    /// Resizing requires that this HBuf owns its memory and is the only reference to it,
+    /// see try_resize for the errors that may occur in that case.
+    /// On error this HBuf remains unmodified.
+    ///
+    pub fn append(&mut self, other: &HBuf) -> Result<(), HBufError> {
+        let new_limit = self.limit.checked_add(other.limit).ok_or(HBufError::LayoutError)?;
+        if new_limit > self.capacity {
+            let limit = self.limit;
+            self.try_resize(new_limit)?;
+            self.limit = limit;
+        }
+
+        unsafe { std::ptr::copy(other.data_ptr.inner(), self.data_ptr.add(self.limit), other.limit) }
+        self.limit = new_limit;
+        Ok(())
+    }
+
+    ///
+    /// Returns the byte at the position without advancing the position.
+    /// Returns an UnexpectedEof error if the position is at the limit.
+    ///
+    pub fn peek_u8(&self) -> io::Result<u8> {
+        Ok(self.peek_slice(1)?[0])
+    }
+
+    ///
+    /// Returns a slice of the given length starting at the position without advancing the position.
+    /// Returns an UnexpectedEof error if less than len bytes remain before the limit.
+    ///
+    pub fn peek_slice(&self, len: usize) -> io::Result<&[u8]> {
+        if self.limit - self.position < len {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "failed to fill entire buffer"));
+        }
+
+        Ok(&self.as_slice()[self.position..self.position + len])
+    }
+
+    ///
+    /// Copies the value T at the position out of the memory without advancing the position.
+    /// This method uses read_unaligned so alignment is irrelevant for this method.
+    /// Returns an UnexpectedEof error if less than size_of T bytes remain before the limit.
+    ///
+    pub unsafe fn peek<T: Sized+Copy>(&self) -> io::Result<T> {
+        let bytes = self.peek_slice(size_of::<T>())?;
+        unsafe { Ok(bytes.as_ptr().cast::<T>().read_unaligned()) }
+    }
+
+    ///
+    /// Ensures that the capacity is at least limit + additional.
+    /// If the capacity is too small then the memory is resized to at least twice the current capacity.
+    /// Resizing requires that this HBuf owns its memory and is the only reference to it,
+    /// see try_resize for the errors that may occur in that case.
+    ///
+    /// An empty HBuf without memory such as HBuf::empty allocates new memory of at least 8 bytes instead.
+    ///
+    /// Resizing may move the memory, which invalidates all previously obtained slices and pointers.
+    ///
+    pub fn reserve(&mut self, additional: usize) -> Result<(), HBufError> {
+        let required = self.limit.checked_add(additional).ok_or(HBufError::LayoutError)?;
+        if required <= self.capacity {
+            return Ok(());
+        }
+
+        if self.capacity == 0 && self.destructor.is_none() {
+            let HBuf { data_ptr, capacity, destructor, .. } = HBuf::try_allocate(required.max(8))?;
+            self.data_ptr = data_ptr;
+            self.capacity = capacity;
+            self.destructor = destructor;
+            return Ok(());
+        }
+
+        let limit = self.limit;
+        self.try_resize(required.max(self.capacity.saturating_mul(2)))?;
+        self.limit = limit;
+        Ok(())
+    }
+
+    ///
+    /// Writes the byte at the limit and increases the limit by 1.
+    /// If the limit is equal to the capacity then the memory is grown using reserve first.
+    /// On error this HBuf remains unmodified.
+    ///
+    pub fn push(&mut self, value: u8) -> Result<(), HBufError> {
+        self.reserve(1)?;
+        unsafe { self.data_ptr.add(self.limit).write(value) }
+        self.limit += 1;
+        Ok(())
+    }
+
+    ///
+    /// Formats all bytes up to the capacity as a hex dump similar to what the application xxd would output.
+    /// Each row is terminated by a newline.
+    ///
+    /// panics if bytes_per_row is 0.
+    ///
+    pub fn hexdump(&self, opts: HexDumpOptions) -> String {
+        let mut result = String::new();
+        self.write_hex_dump(&mut result, &opts).expect("writing to a String never fails");
+        result
+    }
+
+    fn write_hex_dump(&self, f: &mut impl std::fmt::Write, opts: &HexDumpOptions) -> std::fmt::Result {
+        if opts.bytes_per_row == 0 {
+            panic!("bytes_per_row is 0");
+        }
+
+        for idx_base in (0..self.capacity).step_by(opts.bytes_per_row) {
+            if opts.relative_offsets {
+                write!(f, "0x{:08x}:", idx_base)?;
+            } else {
+                write!(f, "0x{:0width$x}:", self.data_ptr.wrapping_add(idx_base) as usize, width = (usize::BITS / 4) as usize)?;
+            }
+
+            for idx in 0..opts.bytes_per_row {
+                if idx_base+idx >= self.capacity && !opts.ascii {
+                    break;
+                }
+                if idx & 1 == 0 {
+                    write!(f, " ")?;
+                }
+                if idx_base+idx >= self.capacity {
+                    write!(f, "  ")?;
+                    continue;
+                }
+                write!(f, "{:02x}", unsafe { *self.data_ptr.add(idx+idx_base) })?;
+            }
+
+            if opts.ascii {
+                write!(f, "  ")?;
+
+                for idx in 0..opts.bytes_per_row {
+                    if idx_base+idx >= self.capacity {
+                        write!(f, " ")?;
+                        continue;
+                    }
+                    let data = unsafe { *self.data_ptr.add(idx+idx_base) } as char;
+                    if char::is_ascii_graphic(&data) {
+                        write!(f, "{}", data)?;
+                    } else {
+                        write!(f, ".")?;
+                    }
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Encodes the bytes up to the limit as lower case hex digits.
+    ///
+    pub fn to_hex_string(&self) -> String {
+        let mut result = String::with_capacity(self.limit * 2);
+        for byte in self.as_slice() {
+            result.push(char::from_digit((byte >> 4) as u32, 16).unwrap());
+            result.push(char::from_digit((byte & 0xF) as u32, 16).unwrap());
+        }
+
+        result
+    }
+
+    ///
+    /// Allocates a HBuf that contains the bytes encoded by the hex string.
+    /// Whitespace is ignored and both lower and upper case digits are accepted.
+    ///
+    /// Fails with HBufError::InvalidHex if the string contains any other characters or an odd number of hex digits
+    /// and with HBufError::ZeroSize if the string contains no hex digits.
+    ///
+    pub fn from_hex_string(hex: &str) -> Result<HBuf, HBufError> {
+        let mut digits = 0usize;
+        for c in hex.chars() {
+            if c.is_ascii_hexdigit() {
+                digits += 1;
+            } else if !c.is_whitespace() {
+                return Err(HBufError::InvalidHex);
+            }
+        }
+
+        if digits % 2 != 0 {
+            return Err(HBufError::InvalidHex);
+        }
+
+        if digits == 0 {
+            return Err(HBufError::ZeroSize);
+        }
+
+        let buf = HBuf::try_allocate(digits / 2)?;
+        let mut index = 0;
+        let mut high = None;
+        for c in hex.chars() {
+            let Some(digit) = c.to_digit(16) else {
+                continue;
+            };
+
+            match high.take() {
+                None => high = Some(digit as u8),
+                Some(value) => {
+                    unsafe { buf.data_ptr.add(index).write(value << 4 | digit as u8) }
+                    index += 1;
+                }
+            }
+        }
+
+        Ok(buf)
+    }
+
+    ///
+    /// Returns the bytes up to the limit or up to the precision of the formatter if it is smaller.
+    ///
+    fn hex_format_bytes(&self, f: &Formatter<'_>) -> &[u8] {
+        let count = f.precision().unwrap_or(self.limit).min(self.limit);
+        &self.as_slice()[..count]
+    }
+
+    ///
+    /// Returns an IoSlice of the bytes between the position and the limit.
+    /// This can be passed to vectored writes such as writev.
+    ///
+    pub fn as_io_slice(&self) -> IoSlice<'_> {
+        IoSlice::new(&self.as_slice()[self.position..])
+    }
+
+    ///
+    /// Returns an IoSliceMut of the bytes between the position and the limit.
+    /// This can be passed to vectored reads such as readv.
+    ///
+    pub fn as_io_slice_mut(&mut self) -> IoSliceMut<'_> {
+        let position = self.position;
+        IoSliceMut::new(&mut self.as_mut_slice()[position..])
+    }
+
+    ///
+    /// Calls the closure with this HBuf and restores the position if the closure returns an error.
+    /// If the closure succeeds then the position is left wherever the closure moved it.
+    /// This is useful for parsers that need to roll back a partially read message.
+    ///
+    /// Only the position is restored. Any writes or changes to the limit done by the closure remain.
+    /// If the closure reduced the limit below the original position then the position is set to the limit.
+    ///
+    pub fn transaction<R>(&mut self, f: impl FnOnce(&mut HBuf) -> io::Result<R>) -> io::Result<R> {
+        let position = self.position;
+        let result = f(self);
+        if result.is_err() {
+            self.position = position.min(self.limit);
+        }
+
+        result
+    }
+
+    ///
+    /// Rotates the bytes up to the limit in place so that the byte at mid becomes the first byte.
+    ///
+    /// panics if mid > limit.
+    ///
+    pub fn rotate_left(&mut self, mid: usize) {
+        if mid > self.limit {
+            panic!("Index {} is out of bounds for HBuf with limit {}", mid, self.limit);
+        }
+
+        self.as_mut_slice().rotate_left(mid);
+    }
+
+    ///
+    /// Rotates the bytes up to the limit in place so that the last k bytes become the first k bytes.
+    ///
+    /// panics if k > limit.
+    ///
+    pub fn rotate_right(&mut self, k: usize) {
+        if k > self.limit {
+            panic!("Index {} is out of bounds for HBuf with limit {}", k, self.limit);
+        }
+
+        self.as_mut_slice().rotate_right(k);
+    }
+
+    ///
+    /// Reverses the order of the bytes up to the limit in place.
+    ///
+    pub fn reverse(&mut self) {
+        self.as_mut_slice().reverse();
+    }
+
+    ///
+    /// Reverses the order of the bytes in the range in place.
+    ///
+    /// panics if the range does not lie within the limit.
+    ///
+    pub fn reverse_range(&mut self, range: Range<usize>) {
+        let (start, end) = self.check_range(range);
+        self.as_mut_slice()[start..end].reverse();
+    }
+
+    ///
+    /// Combines each byte up to the limit with the byte at the same index in other using XOR.
+    /// If the lengths differ then only the overlap of min(limit, other.len()) bytes is modified.
+    ///
+    pub fn xor_with(&mut self, other: &[u8]) {
+        self.combine_with(other, |a, b| a ^ b);
+    }
+
+    ///
+    /// Combines each byte up to the limit with the byte at the same index in other using AND.
+    /// If the lengths differ then only the overlap of min(limit, other.len()) bytes is modified.
+    ///
+    pub fn and_with(&mut self, other: &[u8]) {
+        self.combine_with(other, |a, b| a & b);
+    }
+
+    ///
+    /// Combines each byte up to the limit with the byte at the same index in other using OR.
+    /// If the lengths differ then only the overlap of min(limit, other.len()) bytes is modified.
+    ///
+    pub fn or_with(&mut self, other: &[u8]) {
+        self.combine_with(other, |a, b| a | b);
+    }
+
+    ///
+    /// Applies the bitwise operation 8 bytes at a time and then to the remaining bytes.
+    /// Uses unaligned raw pointer accesses because other may be backed by the same memory as this HBuf.
+    ///
+    #[inline]
+    fn combine_with(&mut self, other: &[u8], op: impl Fn(u64, u64) -> u64) {
+        let len = self.limit.min(other.len());
+        let wide = len - len % size_of::<u64>();
+        let dst = self.data_ptr.inner();
+        let src = other.as_ptr();
+
+        unsafe {
+            for index in (0..wide).step_by(size_of::<u64>()) {
+                let a = dst.add(index).cast::<u64>().read_unaligned();
+                let b = src.add(index).cast::<u64>().read_unaligned();
+                dst.add(index).cast::<u64>().write_unaligned(op(a, b));
+            }
+
+            for index in wide..len {
+                *dst.add(index) = op(*dst.add(index) as u64, *src.add(index) as u64) as u8;
+            }
+        }
+    }
+
+    ///
+    /// Returns the amount of bytes up to the limit that are equal to the given value.
+    ///
+    pub fn count_byte(&self, value: u8) -> usize {
+        #[cfg(feature = "memchr_support")]
+        let count = memchr::memchr_iter(value, self.as_slice()).count();
+        #[cfg(not(feature = "memchr_support"))]
+        let count = self.as_slice().iter().filter(|b| **b == value).count();
+
+        count
+    }
+
+    ///
+    /// Replaces every byte up to the limit that is equal to from with to.
+    /// The loop is branch free so that the compiler can vectorize it.
+    ///
+    /// Returns the amount of bytes that were replaced.
+    ///
+    pub fn replace_byte(&mut self, from: u8, to: u8) -> usize {
+        let mut count = 0usize;
+        for byte in self.as_mut_slice() {
+            let hit = *byte == from;
+            count += hit as usize;
+            *byte = if hit { to } else { *byte };
+        }
+
+        count
+    }
+
+    ///
+    /// Replaces every non overlapping occurrence of from up to the limit with to.
+    /// Occurrences are searched from the start and the search resumes after each replaced occurrence.
+    /// An empty from is never replaced.
+    ///
+    /// Returns the amount of occurrences that were replaced.
+    /// panics if from and to do not have the same length.
+    ///
+    pub fn replace_bytes(&mut self, from: &[u8], to: &[u8]) -> usize {
+        if from.len() != to.len() {
+            panic!("Cannot replace {} bytes with {} bytes", from.len(), to.len());
+        }
+
+        if from.is_empty() {
+            return 0;
+        }
+
+        let mut count = 0usize;
+        let mut offset = 0usize;
+        while let Some(found) = Self::find_subslice_in(&self.as_slice()[offset..], from) {
+            let index = offset + found;
+            self.as_mut_slice()[index..index + to.len()].copy_from_slice(to);
+            offset = index + from.len();
+            count += 1;
+        }
+
+        count
+    }
+
+    ///
+    /// Returns how often each byte value occurs in the bytes up to the limit.
+    /// The count of the value x is stored at index x.
+    ///
+    pub fn byte_histogram(&self) -> [usize; 256] {
+        let mut histogram = [0usize; 256];
+        for byte in self.as_slice() {
+            histogram[*byte as usize] += 1;
+        }
+
+        histogram
+    }
+
+    ///
+    /// Returns the shannon entropy of the bytes up to the limit in bits per byte.
+    /// The result lies between 0.0 (only one distinct value or empty) and 8.0 (all values equally common).
+    /// Compressed or encrypted data usually has an entropy close to 8.0.
+    ///
+    pub fn shannon_entropy(&self) -> f64 {
+        if self.limit == 0 {
+            return 0.0;
+        }
+
+        let total = self.limit as f64;
+        self.byte_histogram().iter()
+            .filter(|count| **count != 0)
+            .map(|count| {
+                let p = *count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    ///
+    /// Swaps this HBuf with the other HBuf. Only the handles are swapped, no bytes are copied.
+    /// Afterwards each HBuf refers to the memory, limit, position and mark that the other one had before.
+    ///
+    /// This shadows slice::swap, use swap_bytes_with to exchange the contents of two buffers
+    /// or as_mut_slice().swap(a, b) to swap two bytes within the buffer.
+    ///
+    pub fn swap(&mut self, other: &mut HBuf) {
+        std::mem::swap(self, other);
+    }
+
+    ///
+    /// Exchanges the bytes up to the limit of this HBuf with the bytes up to the limit of the other HBuf.
+    /// Unlike swap this copies the data and leaves both handles referring to their original memory.
+    ///
+    /// panics if the limits are not equal.
+    ///
+    pub fn swap_bytes_with(&mut self, other: &mut HBuf) {
+        if self.limit != other.limit {
+            panic!("Cannot swap the bytes of a HBuf with limit {} with the bytes of a HBuf with limit {}", self.limit, other.limit);
+        }
+
+        let a = self.data_ptr.inner();
+        let b = other.data_ptr.inner();
+        let len = self.limit;
+        unsafe {
+            if a.wrapping_add(len) <= b || b.wrapping_add(len) <= a {
+                std::ptr::swap_nonoverlapping(a, b, len);
+                return;
+            }
+
+            //Both buffers are backed by overlapping memory, swapping byte by byte is still well defined.
+            for index in 0..len {
+                std::ptr::swap(a.add(index), b.add(index));
+            }
+        }
+    }
+
+    ///
+    /// Hints the CPU to load the cache line containing the byte at index because it will be read soon.
+    /// This is a noop on targets other than x86, x86_64 and aarch64 and if index is not below the limit.
+    ///
+    #[inline]
+    pub fn prefetch_read(&self, index: usize) {
+        if index >= self.limit {
+            return;
+        }
+
+        let ptr = self.data_ptr.wrapping_add(index);
+        unsafe {
+            #[cfg(target_arch = "x86_64")]
+            std::arch::x86_64::_mm_prefetch::<{ std::arch::x86_64::_MM_HINT_T0 }>(ptr.cast::<i8>());
+            #[cfg(target_arch = "x86")]
+            std::arch::x86::_mm_prefetch::<{ std::arch::x86::_MM_HINT_T0 }>(ptr.cast::<i8>());
+            #[cfg(target_arch = "aarch64")]
+            std::arch::asm!("prfm pldl1keep, [{0}]", in(reg) ptr, options(nostack, preserves_flags, readonly));
+            #[cfg(not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64")))]
+            let _ = ptr;
+        }
+    }
+
+    ///
+    /// Hints the CPU to load the cache line containing the byte at index because it will be written soon.
+    /// This is a noop on targets other than x86, x86_64 and aarch64 and if index is not below the limit.
+    ///
+    #[inline]
+    pub fn prefetch_write(&self, index: usize) {
+        if index >= self.limit {
+            return;
+        }
+
+        let ptr = self.data_ptr.wrapping_add(index);
+        unsafe {
+            #[cfg(target_arch = "x86_64")]
+            std::arch::x86_64::_mm_prefetch::<{ std::arch::x86_64::_MM_HINT_ET0 }>(ptr.cast::<i8>());
+            #[cfg(target_arch = "x86")]
+            std::arch::x86::_mm_prefetch::<{ std::arch::x86::_MM_HINT_ET0 }>(ptr.cast::<i8>());
+            #[cfg(target_arch = "aarch64")]
+            std::arch::asm!("prfm pstl1keep, [{0}]", in(reg) ptr, options(nostack, preserves_flags, readonly));
+            #[cfg(not(any(target_arch = "x86_64", target_arch = "x86", target_arch = "aarch64")))]
+            let _ = ptr;
+        }
+    }
+
     fn seek_start(&mut self, from: u64) -> bool {
         if from > self.limit as u64 {
             return false;
@@ -1107,15 +3467,29 @@ impl Seek for HBuf {
 
 impl Write for HBuf {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
-        let to_copy = buf.len().min(self.position-self.limit);
+        let to_copy = buf.len().min(self.limit-self.position);
         if to_copy == 0 {
             return Ok(0);
         }
 
+        unsafe { std::ptr::copy(buf.as_ptr(), self.data_ptr.wrapping_add(self.position), to_copy) }
         self.position = self.position + to_copy;
         Ok(to_copy)
     }
 
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs {
+            let written = self.write(buf)?;
+            total += written;
+            if written < buf.len() {
+                break;
+            }
+        }
+
+        Ok(total)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         //NOOP
         Ok(())
@@ -1138,7 +3512,7 @@ impl Write for HBuf {
 
 impl Read for HBuf {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        let to_copy = buf.len().min(self.position-self.limit);
+        let to_copy = buf.len().min(self.limit-self.position);
         if to_copy == 0 {
             return Ok(0);
         }
@@ -1147,8 +3521,21 @@ impl Read for HBuf {
         Ok(to_copy)
     }
 
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs {
+            let read = self.read(buf)?;
+            total += read;
+            if read < buf.len() {
+                break;
+            }
+        }
+
+        Ok(total)
+    }
+
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
-        let to_copy = self.position-self.limit;
+        let to_copy = self.limit-self.position;
         if to_copy == 0 {
             return Ok(0);
         }
@@ -1172,6 +3559,75 @@ impl Read for HBuf {
     }
 }
 
+///
+/// The entire buffer is already in memory so fill_buf never blocks and returns the bytes between position and limit.
+///
+impl BufRead for HBuf {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let position = self.position;
+        Ok(&self.as_slice()[position..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position = self.position.saturating_add(amt).min(self.limit);
+    }
+}
+
+///
+/// Takes ownership of the allocation of the Vec without copying.
+/// The capacity of the HBuf is the capacity of the Vec and the limit is the length of the Vec.
+/// The Vec is reconstructed and dropped once no more references to the HBuf exist.
+///
+impl From<Vec<u8>> for HBuf {
+    fn from(value: Vec<u8>) -> Self {
+        let mut value = ManuallyDrop::new(value);
+        let data = unsafe { value.as_mut_ptr().as_sync_mut() };
+        let capacity = value.capacity();
+        HBuf {
+            data_ptr: data,
+            capacity,
+            limit: value.len(),
+            position: 0,
+            mark: None,
+            destructor: Arc::new(Some(HBufDestructor::new(data, capacity, HBufDestructorInfo::Vec))),
+            shared_limit: None
+        }
+    }
+}
+
+///
+/// Reclaims the allocation of a HBuf that was created from a Vec<u8> without copying.
+/// The length of the Vec is the limit of the HBuf.
+/// Fails with HBufError::Shared if other references to the memory exist
+/// and with HBufError::NotOwned if the HBuf was not created from a Vec or is only a split of it.
+///
+impl TryFrom<HBuf> for Vec<u8> {
+    type Error = HBufError;
+
+    fn try_from(mut value: HBuf) -> Result<Self, Self::Error> {
+        value.take_vec()
+    }
+}
+
+///
+/// Takes ownership of the allocation of the boxed slice without copying.
+/// The boxed slice is reconstructed and dropped once no more references to the HBuf exist.
+///
+impl From<Box<[u8]>> for HBuf {
+    fn from(value: Box<[u8]>) -> Self {
+        HBuf::from(Vec::from(value))
+    }
+}
+
+///
+/// Returns HBuf::empty, which does not allocate.
+///
+impl Default for HBuf {
+    fn default() -> Self {
+        HBuf::empty()
+    }
+}
+
 impl Clone for HBuf {
     fn clone(&self) -> Self {
         HBuf {
@@ -1179,7 +3635,9 @@ impl Clone for HBuf {
             capacity: self.capacity,
             limit: self.limit,
             position: self.position,
+            mark: self.mark,
             destructor: self.destructor.clone(),
+            shared_limit: self.shared_limit.clone(),
         }
     }
 }
@@ -1205,6 +3663,33 @@ impl IndexMut<usize> for HBuf {
     }
 }
 
+macro_rules! range_index {
+    ($range:ty) => {
+        impl Index<$range> for HBuf {
+            type Output = [u8];
+
+            fn index(&self, index: $range) -> &Self::Output {
+                let (start, end) = self.check_range(index);
+                unsafe { std::slice::from_raw_parts(self.data_ptr.wrapping_add(start), end-start) }
+            }
+        }
+
+        impl IndexMut<$range> for HBuf {
+            fn index_mut(&mut self, index: $range) -> &mut Self::Output {
+                let (start, end) = self.check_range(index);
+                unsafe { std::slice::from_raw_parts_mut(self.data_ptr.wrapping_add(start), end-start) }
+            }
+        }
+    };
+}
+
+range_index!(Range<usize>);
+range_index!(RangeFrom<usize>);
+range_index!(RangeTo<usize>);
+range_index!(RangeFull);
+range_index!(RangeInclusive<usize>);
+range_index!(RangeToInclusive<usize>);
+
 impl Deref for HBuf {
     type Target = [u8];
 
@@ -1219,6 +3704,123 @@ impl DerefMut for HBuf {
     }
 }
 
+impl<'a> IntoIterator for &'a HBuf {
+    type Item = &'a u8;
+    type IntoIter = std::slice::Iter<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut HBuf {
+    type Item = &'a mut u8;
+    type IntoIter = std::slice::IterMut<'a, u8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl IntoIterator for HBuf {
+    type Item = u8;
+    type IntoIter = HBufIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let end = self.limit;
+        HBufIntoIter {
+            buf: self,
+            index: 0,
+            end
+        }
+    }
+}
+
+///
+/// Consuming iterator over the bytes of a HBuf up to its limit.
+/// The iterator holds the HBuf so the memory stays alive until the iterator is dropped.
+///
+#[derive(Debug, Clone)]
+pub struct HBufIntoIter {
+    buf: HBuf,
+    index: usize,
+    end: usize
+}
+
+impl HBufIntoIter {
+    ///
+    /// Returns the bytes that have not yet been yielded by the iterator.
+    ///
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buf.as_slice()[self.index..self.end]
+    }
+
+    ///
+    /// Returns the HBuf the iterator was created from.
+    ///
+    pub fn into_inner(self) -> HBuf {
+        self.buf
+    }
+}
+
+impl Iterator for HBufIntoIter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        let value = self.buf[self.index];
+        self.index += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl DoubleEndedIterator for HBufIntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(self.buf[self.end])
+    }
+}
+
+impl ExactSizeIterator for HBufIntoIter {}
+
+impl FusedIterator for HBufIntoIter {}
+
+impl AsRef<[u8]> for HBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsMut<[u8]> for HBuf {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl Borrow<[u8]> for HBuf {
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl BorrowMut<[u8]> for HBuf {
+    fn borrow_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn test_sync() {
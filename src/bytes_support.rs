@@ -0,0 +1,49 @@
+use bytes::buf::UninitSlice;
+use bytes::{Buf, BufMut};
+use crate::HBuf;
+
+///
+/// Reads from position to limit. Advancing moves the position.
+///
+/// Note: HBuf has inherent get_* methods that take an index.
+/// Use Buf::get_u16(&mut buf) syntax to call the methods of this trait.
+///
+impl Buf for HBuf {
+    fn remaining(&self) -> usize {
+        HBuf::remaining(self)
+    }
+
+    fn chunk(&self) -> &[u8] {
+        &self.as_slice()[self.position()..]
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        if cnt > HBuf::remaining(self) {
+            panic!("Cannot advance by {} bytes because only {} bytes are remaining", cnt, HBuf::remaining(self));
+        }
+
+        self.set_position(self.position() + cnt);
+    }
+}
+
+///
+/// Writes from position to limit. Advancing moves the position.
+///
+unsafe impl BufMut for HBuf {
+    fn remaining_mut(&self) -> usize {
+        HBuf::remaining(self)
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        if cnt > HBuf::remaining(self) {
+            panic!("Cannot advance by {} bytes because only {} bytes are remaining", cnt, HBuf::remaining(self));
+        }
+
+        self.set_position(self.position() + cnt);
+    }
+
+    fn chunk_mut(&mut self) -> &mut UninitSlice {
+        let position = self.position();
+        UninitSlice::new(&mut self.as_mut_slice()[position..])
+    }
+}
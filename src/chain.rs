@@ -0,0 +1,54 @@
+use std::io;
+use std::io::Read;
+use crate::HBuf;
+
+///
+/// Read adapter that reads the bytes between position and limit of the first HBuf followed by those of the second HBuf.
+/// Reading advances the positions of the HBuf's.
+/// This is similar to Read::chain but knows the total amount of remaining bytes.
+///
+#[derive(Debug)]
+pub struct ChainedHBuf {
+    first: HBuf,
+    second: HBuf
+}
+
+impl HBuf {
+
+    ///
+    /// Returns a ChainedHBuf that reads the remaining bytes of this HBuf followed by the remaining bytes of the other HBuf.
+    ///
+    pub fn chain(self, other: HBuf) -> ChainedHBuf {
+        ChainedHBuf {
+            first: self,
+            second: other
+        }
+    }
+}
+
+impl ChainedHBuf {
+
+    ///
+    /// Returns the total amount of bytes that can still be read from both HBuf's.
+    ///
+    pub fn remaining(&self) -> usize {
+        self.first.remaining() + self.second.remaining()
+    }
+
+    ///
+    /// Returns both HBuf's. Their positions reflect how much was read.
+    ///
+    pub fn into_inner(self) -> (HBuf, HBuf) {
+        (self.first, self.second)
+    }
+}
+
+impl Read for ChainedHBuf {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.first.remaining() > 0 {
+            return self.first.read(buf);
+        }
+
+        self.second.read(buf)
+    }
+}
@@ -0,0 +1,35 @@
+use std::ops::Range;
+use crate::HBuf;
+
+impl HBuf {
+
+    ///
+    /// Calculates the CRC32 checksum of all bytes up to the limit.
+    ///
+    pub fn crc32(&self) -> u32 {
+        crc32fast::hash(self.as_slice())
+    }
+
+    ///
+    /// Calculates the CRC32 checksum of all bytes in the range.
+    /// panics if the range does not lie within the limit.
+    ///
+    pub fn crc32_range(&self, range: Range<usize>) -> u32 {
+        crc32fast::hash(&self[range])
+    }
+
+    ///
+    /// Calculates the Adler32 checksum of all bytes up to the limit.
+    ///
+    pub fn adler32(&self) -> u32 {
+        adler::adler32_slice(self.as_slice())
+    }
+
+    ///
+    /// Calculates the Adler32 checksum of all bytes in the range.
+    /// panics if the range does not lie within the limit.
+    ///
+    pub fn adler32_range(&self, range: Range<usize>) -> u32 {
+        adler::adler32_slice(&self[range])
+    }
+}
@@ -1,17 +1,27 @@
 use std::alloc::Layout;
-use sync_ptr::SyncMutPtr;
-use crate::DynDestructor;
+use std::mem::ManuallyDrop;
+use std::sync::atomic::{compiler_fence, Ordering};
+#[cfg(any(feature = "mlock_support", all(feature = "mprotect_support", unix)))]
+use std::sync::atomic::AtomicBool;
+use sync_ptr::{FromMutPtr, SyncMutPtr};
+use crate::{DynDestructor, HBufError};
 
 #[derive(Debug)]
 pub(crate) struct HBufDestructor {
     data_ptr: SyncMutPtr<u8>,
     capacity: usize,
-    destructor_info: HBufDestructorInfo
+    destructor_info: HBufDestructorInfo,
+    #[cfg(all(feature = "mprotect_support", unix))]
+    protected: AtomicBool,
+    #[cfg(feature = "mlock_support")]
+    locked: AtomicBool
 }
 
 #[derive(Debug)]
 pub(crate) enum HBufDestructorInfo {
     Layout(Layout),
+    SecureLayout(Layout),
+    Vec,
     Destructor(fn(*mut u8, usize)),
     DynDestructor(Box<dyn DynDestructor>)
 }
@@ -21,21 +31,155 @@ impl HBufDestructor {
         HBufDestructor {
             data_ptr,
             capacity,
-            destructor_info
+            destructor_info,
+            #[cfg(all(feature = "mprotect_support", unix))]
+            protected: AtomicBool::new(false),
+            #[cfg(feature = "mlock_support")]
+            locked: AtomicBool::new(false)
         }
     }
+
+    ///
+    /// Remembers that the memory was locked by SecureHBuf, so that it is unlocked after it is wiped.
+    ///
+    #[cfg(feature = "mlock_support")]
+    pub(crate) fn mark_locked(&self) {
+        self.locked.store(true, Ordering::SeqCst);
+    }
+
+    ///
+    /// Remembers that pages of the memory were protected by HBuf::protect,
+    /// so that they are made readable and writable again before the memory is freed.
+    ///
+    #[cfg(all(feature = "mprotect_support", unix))]
+    pub(crate) fn mark_protected(&self) {
+        self.protected.store(true, Ordering::SeqCst);
+    }
+
+    ///
+    /// Makes all pages of the memory readable and writable again if they were protected by HBuf::protect.
+    ///
+    fn reset_protection(&mut self) {
+        #[cfg(all(feature = "mprotect_support", unix))]
+        if *self.protected.get_mut() {
+            unsafe { crate::mprotect_support::unprotect(self.data_ptr.inner(), self.capacity) }
+            *self.protected.get_mut() = false;
+        }
+    }
+
+    ///
+    /// Changes the destructor of memory allocated by the rust allocator to zero the memory before deallocating it.
+    ///
+    pub(crate) fn make_secure(&mut self) {
+        if let HBufDestructorInfo::Layout(layout) = self.destructor_info {
+            self.destructor_info = HBufDestructorInfo::SecureLayout(layout);
+        }
+    }
+
+    ///
+    /// Returns the alignment the memory was allocated with if it was allocated by the rust allocator.
+    ///
+    pub(crate) fn layout_alignment(&self) -> Option<usize> {
+        match self.destructor_info {
+            HBufDestructorInfo::Layout(layout) => Some(layout.align()),
+            HBufDestructorInfo::SecureLayout(layout) => Some(layout.align()),
+            _ => None
+        }
+    }
+
+    ///
+    /// Returns true if the memory is zeroed before it is deallocated.
+    ///
+    pub(crate) fn is_secure(&self) -> bool {
+        matches!(self.destructor_info, HBufDestructorInfo::SecureLayout(_))
+    }
+
+    ///
+    /// Returns true if the memory was allocated by the rust allocator and the given pointer and capacity
+    /// describe the entire allocation.
+    ///
+    pub(crate) fn owns_allocation(&self, data_ptr: SyncMutPtr<u8>, capacity: usize) -> bool {
+        match self.destructor_info {
+            HBufDestructorInfo::Layout(_) => self.data_ptr.inner() == data_ptr.inner() && self.capacity == capacity,
+            _ => false
+        }
+    }
+
+    ///
+    /// Returns true if the memory was taken from a Vec<u8> and the given pointer and capacity
+    /// describe the entire allocation of the Vec.
+    ///
+    pub(crate) fn owns_vec(&self, data_ptr: SyncMutPtr<u8>, capacity: usize) -> bool {
+        match self.destructor_info {
+            HBufDestructorInfo::Vec => self.data_ptr.inner() == data_ptr.inner() && self.capacity == capacity,
+            _ => false
+        }
+    }
+
+    ///
+    /// Turns memory that was taken from a Vec<u8> back into the Vec without running the destructor.
+    /// Caller must ensure that owns_vec returned true and that len is not larger than the capacity.
+    ///
+    pub(crate) unsafe fn into_vec(mut self, len: usize) -> Vec<u8> {
+        self.reset_protection();
+        let destructor = ManuallyDrop::new(self);
+        Vec::from_raw_parts(destructor.data_ptr.inner(), len, destructor.capacity)
+    }
+
+    ///
+    /// Reallocates memory that was allocated by the rust allocator preserving its alignment.
+    /// On failure the old allocation remains untouched.
+    ///
+    pub(crate) fn realloc(&mut self, new_size: usize) -> Result<SyncMutPtr<u8>, HBufError> {
+        let layout = match self.destructor_info {
+            HBufDestructorInfo::Layout(layout) => layout,
+            _ => return Err(HBufError::NotOwned)
+        };
+
+        self.reset_protection();
+        let new_layout = Layout::from_size_align(new_size, layout.align())?;
+        let data = unsafe { std::alloc::realloc(self.data_ptr.inner(), layout, new_size) };
+        if data.is_null() {
+            return Err(HBufError::OutOfMemory);
+        }
+
+        self.data_ptr = unsafe { data.as_sync_mut() };
+        self.capacity = new_size;
+        self.destructor_info = HBufDestructorInfo::Layout(new_layout);
+        Ok(self.data_ptr)
+    }
 }
 
 impl Drop for HBufDestructor {
     fn drop(&mut self) {
+        self.reset_protection();
         match &mut self.destructor_info {
             HBufDestructorInfo::Layout(lay) => unsafe { std::alloc::dealloc(self.data_ptr.inner(), *lay) }
+            HBufDestructorInfo::SecureLayout(lay) => unsafe {
+                secure_zero(self.data_ptr.inner(), self.capacity);
+                #[cfg(feature = "mlock_support")]
+                if *self.locked.get_mut() {
+                    let _ = crate::mlock_support::unlock(self.data_ptr.inner(), self.capacity);
+                }
+                std::alloc::dealloc(self.data_ptr.inner(), *lay)
+            }
+            HBufDestructorInfo::Vec => unsafe { drop(Vec::from_raw_parts(self.data_ptr.inner(), 0, self.capacity)) }
             HBufDestructorInfo::Destructor(destructor_fn) => destructor_fn(self.data_ptr.inner(), self.capacity),
             HBufDestructorInfo::DynDestructor(destructor) => destructor.destroy(self.data_ptr.inner(), self.capacity)
         }
     }
 }
 
+///
+/// Overwrites the memory with zeros using volatile writes so that the compiler cannot elide the writes.
+///
+pub(crate) unsafe fn secure_zero(ptr: *mut u8, size: usize) {
+    for x in 0..size {
+        std::ptr::write_volatile(ptr.add(x), 0);
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
 #[cfg(test)]
 #[test]
 fn test_sync() {
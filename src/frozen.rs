@@ -0,0 +1,166 @@
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::atomic::Ordering;
+use crate::HBuf;
+
+///
+/// A HBuf that only exposes read access to its memory.
+/// It implements Deref to [u8] but not DerefMut or IndexMut, so it can be handed out to many readers
+/// as an immutable snapshot.
+///
+/// Clones share the memory just like clones of HBuf do.
+///
+#[derive(Debug, Clone)]
+pub struct FrozenHBuf(HBuf);
+
+macro_rules! frozen_get {
+    ($type:ty, $get_name:ident, $try_get_name:ident) => {
+
+        ///
+        /// Reads a the value at the given offset, see HBuf.
+        /// panics on out of bounds.
+        ///
+        pub fn $get_name(&self, index: usize) -> $type {
+            self.0.$get_name(index)
+        }
+
+        ///
+        /// Reads a the value at the given offset, see HBuf.
+        /// returns None on out of bounds.
+        ///
+        pub fn $try_get_name(&self, index: usize) -> Option<$type> {
+            self.0.$try_get_name(index)
+        }
+    };
+}
+
+macro_rules! frozen_load {
+    ($type:ty, $load_name:ident) => {
+
+        ///
+        /// Atomic "get" with memory ordering semantics, see HBuf.
+        ///
+        pub fn $load_name(&self, index: usize, ordering: Ordering) -> $type {
+            self.0.$load_name(index, ordering)
+        }
+    };
+}
+
+impl HBuf {
+
+    ///
+    /// Turns this HBuf into a FrozenHBuf that only allows reading the memory.
+    ///
+    pub fn freeze(self) -> FrozenHBuf {
+        FrozenHBuf(self)
+    }
+}
+
+impl FrozenHBuf {
+
+    ///
+    /// Turns this FrozenHBuf back into a HBuf that allows writing the memory.
+    /// Other clones of the FrozenHBuf still share the memory and will observe writes made through the returned HBuf.
+    ///
+    pub fn thaw(self) -> HBuf {
+        self.0
+    }
+
+    ///
+    /// Returns the pointer to the start of the memory.
+    ///
+    pub fn as_ptr(&self) -> *const u8 {
+        self.0.as_ptr()
+    }
+
+    ///
+    /// Returns a slice of the bytes up to the limit.
+    ///
+    pub fn as_slice(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+
+    ///
+    /// Returns the capacity of the memory.
+    ///
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    ///
+    /// Returns the limit, the amount of readable bytes.
+    ///
+    pub fn limit(&self) -> usize {
+        self.0.limit()
+    }
+
+    frozen_get!(u8, get_u8, try_get_u8);
+    frozen_get!(i8, get_i8, try_get_i8);
+    frozen_get!(u16, get_u16, try_get_u16);
+    frozen_get!(i16, get_i16, try_get_i16);
+    frozen_get!(u32, get_u32, try_get_u32);
+    frozen_get!(i32, get_i32, try_get_i32);
+    frozen_get!(u64, get_u64, try_get_u64);
+    frozen_get!(i64, get_i64, try_get_i64);
+    frozen_get!(u128, get_u128, try_get_u128);
+    frozen_get!(i128, get_i128, try_get_i128);
+    frozen_get!(usize, get_usize, try_get_usize);
+    frozen_get!(isize, get_isize, try_get_isize);
+    frozen_get!(f32, get_f32, try_get_f32);
+    frozen_get!(f64, get_f64, try_get_f64);
+
+    #[cfg(target_has_atomic = "8")]
+    frozen_load!(u8, load_u8);
+    #[cfg(target_has_atomic = "8")]
+    frozen_load!(i8, load_i8);
+    #[cfg(target_has_atomic = "16")]
+    frozen_load!(u16, atomic_load_u16);
+    #[cfg(target_has_atomic = "16")]
+    frozen_load!(i16, atomic_load_i16);
+    #[cfg(target_has_atomic = "32")]
+    frozen_load!(u32, atomic_load_u32);
+    #[cfg(target_has_atomic = "32")]
+    frozen_load!(i32, atomic_load_i32);
+    #[cfg(target_has_atomic = "64")]
+    frozen_load!(u64, atomic_load_u64);
+    #[cfg(target_has_atomic = "64")]
+    frozen_load!(i64, atomic_load_i64);
+    #[cfg(target_has_atomic = "ptr")]
+    frozen_load!(usize, atomic_load_usize);
+    #[cfg(target_has_atomic = "ptr")]
+    frozen_load!(isize, atomic_load_isize);
+}
+
+impl From<HBuf> for FrozenHBuf {
+    fn from(value: HBuf) -> Self {
+        value.freeze()
+    }
+}
+
+impl Deref for FrozenHBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for FrozenHBuf {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+impl PartialEq for FrozenHBuf {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for FrozenHBuf {}
+
+impl Hash for FrozenHBuf {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
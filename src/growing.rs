@@ -0,0 +1,53 @@
+use std::io;
+use std::io::Write;
+use crate::HBuf;
+
+///
+/// Write adapter that grows the HBuf instead of failing once the limit is reached.
+/// Bytes are written at the position of the HBuf. If they do not fit before the limit then the limit is increased,
+/// and if they do not fit before the capacity then the memory is grown using HBuf::reserve.
+/// This allows io::copy of a stream of unknown length into a HBuf, which may start out as HBuf::empty.
+///
+/// Growing requires that the HBuf owns its memory and is the only reference to it, otherwise writing fails
+/// with the error of HBuf::reserve. Growing may move the memory, which invalidates all previously obtained slices and pointers.
+///
+#[derive(Debug)]
+pub struct GrowingWriter<'a>(pub &'a mut HBuf);
+
+impl HBuf {
+
+    ///
+    /// Returns a GrowingWriter that writes into this HBuf and grows it on demand.
+    ///
+    pub fn growing_writer(&mut self) -> GrowingWriter<'_> {
+        GrowingWriter(self)
+    }
+}
+
+impl GrowingWriter<'_> {
+    fn ensure_limit(&mut self, end: usize) -> io::Result<()> {
+        if end <= self.0.limit() {
+            return Ok(());
+        }
+
+        if end > self.0.capacity() {
+            self.0.reserve(end - self.0.limit())?;
+        }
+
+        self.0.set_limit(end);
+        Ok(())
+    }
+}
+
+impl Write for GrowingWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let end = self.0.position().checked_add(buf.len())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::OutOfMemory, "size overflows"))?;
+        self.ensure_limit(end)?;
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use std::io;
+use crate::{DynDestructor, HBuf, HBufError};
+
+const HUGE_PAGE_SIZE: usize = 2 * 1024 * 1024;
+
+#[derive(Debug)]
+struct MunmapDestructor {
+    length: usize
+}
+
+impl DynDestructor for MunmapDestructor {
+    fn destroy(&mut self, ptr: *mut u8, _size: usize) {
+        unsafe {
+            //Nothing sensible can be done with the error here.
+            libc::munmap(ptr.cast(), self.length);
+        }
+    }
+}
+
+impl HBuf {
+
+    ///
+    /// Allocates memory that is backed by 2 MiB huge pages using mmap with MAP_HUGETLB.
+    /// Capacity and limit are set to size, the mapping itself is rounded up to a multiple of 2 MiB.
+    /// The memory is zeroed and aligned to 2 MiB. It is unmapped once no more references to the HBuf exist.
+    ///
+    /// Returns an error if size is 0 or if no huge pages are available,
+    /// for example because /proc/sys/vm/nr_hugepages is 0.
+    ///
+    pub fn allocate_hugepage(size: usize) -> io::Result<HBuf> {
+        if size == 0 {
+            return Err(HBufError::ZeroSize.into());
+        }
+
+        let length = size.checked_next_multiple_of(HUGE_PAGE_SIZE).ok_or(HBufError::LayoutError)?;
+        let ptr = unsafe {
+            libc::mmap(std::ptr::null_mut(),
+                       length,
+                       libc::PROT_READ | libc::PROT_WRITE,
+                       libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | libc::MAP_HUGETLB,
+                       -1,
+                       0)
+        };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(unsafe { HBuf::from_raw_parts_with_dyn_destructor(ptr.cast(), size, Box::new(MunmapDestructor { length })) })
+    }
+}
@@ -1,5 +1,52 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 mod buf;
 mod destructor;
+mod typed;
+mod growing;
+mod frozen;
+mod take;
+mod chain;
+mod ordered;
+mod secure;
+#[cfg(feature = "bytes_support")]
+mod bytes_support;
+#[cfg(feature = "serde_support")]
+mod serde_support;
+#[cfg(feature = "tokio_support")]
+mod tokio_support;
+#[cfg(feature = "mmap_support")]
+mod mmap_support;
+#[cfg(feature = "checksum_support")]
+mod checksum_support;
+#[cfg(feature = "base64_support")]
+mod base64_support;
+#[cfg(feature = "mlock_support")]
+mod mlock_support;
+#[cfg(all(feature = "hugepage_support", target_os = "linux"))]
+mod hugepage_support;
+#[cfg(feature = "rand_support")]
+mod rand_support;
+#[cfg(all(feature = "madvise_support", unix))]
+mod madvise_support;
+#[cfg(feature = "page_support")]
+mod page_support;
+#[cfg(all(feature = "mprotect_support", unix))]
+mod mprotect_support;
+#[cfg(feature = "allocator_api")]
+mod allocator_support;
 
-pub use buf::{*};
\ No newline at end of file
+pub use buf::{*};
+pub use typed::TypedHBuf;
+pub use growing::GrowingWriter;
+pub use frozen::FrozenHBuf;
+pub use take::TakeHBuf;
+pub use chain::ChainedHBuf;
+pub use ordered::{ByteOrder, OrderedHBuf};
+pub use secure::SecureHBuf;
+#[cfg(feature = "base64_support")]
+pub use base64_support::Base64Alphabet;
+#[cfg(all(feature = "madvise_support", unix))]
+pub use madvise_support::Advice;
+#[cfg(all(feature = "mprotect_support", unix))]
+pub use mprotect_support::Protection;
@@ -0,0 +1,84 @@
+use std::io;
+use crate::HBuf;
+
+///
+/// Access pattern hints that can be given to the kernel with HBuf::advise.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Advice {
+    ///
+    /// No special treatment. This undoes any previous advice.
+    ///
+    Normal,
+    ///
+    /// The memory will be accessed in ascending order, the kernel may read ahead aggressively.
+    ///
+    Sequential,
+    ///
+    /// The memory will be accessed in random order, the kernel should not read ahead.
+    ///
+    Random,
+    ///
+    /// The memory will be accessed soon, the kernel may start loading it.
+    ///
+    WillNeed,
+    ///
+    /// The memory will not be accessed in the near future, the kernel may free the pages.
+    /// On linux the contents of private anonymous memory (such as memory allocated by HBuf::allocate)
+    /// are discarded and subsequent reads return zeros. Only use this for memory whose contents are no longer needed.
+    ///
+    DontNeed
+}
+
+impl Advice {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            Advice::Normal => libc::MADV_NORMAL,
+            Advice::Sequential => libc::MADV_SEQUENTIAL,
+            Advice::Random => libc::MADV_RANDOM,
+            Advice::WillNeed => libc::MADV_WILLNEED,
+            Advice::DontNeed => libc::MADV_DONTNEED
+        }
+    }
+}
+
+impl HBuf {
+
+    ///
+    /// Gives the kernel a hint about how the memory of this HBuf up to the capacity will be accessed using madvise.
+    /// This is only a hint that the kernel is free to ignore.
+    ///
+    /// madvise operates on whole pages. For Advice::DontNeed only the pages that lie entirely within this HBuf
+    /// are advised so that no neighbouring memory is discarded. For all other advice every page that
+    /// contains at least one byte of this HBuf is advised. This is a noop if the capacity is 0.
+    ///
+    pub fn advise(&self, advice: Advice) -> io::Result<()> {
+        if self.capacity() == 0 {
+            return Ok(());
+        }
+
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if page_size <= 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let page_size = page_size as usize;
+        let start = self.as_ptr() as usize;
+        let end = start + self.capacity();
+
+        let (start, end) = match advice {
+            Advice::DontNeed => (start.next_multiple_of(page_size), end - end % page_size),
+            _ => (start - start % page_size, end.next_multiple_of(page_size))
+        };
+
+        if start >= end {
+            return Ok(());
+        }
+
+        if unsafe { libc::madvise(start as *mut libc::c_void, end - start, advice.as_raw()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use std::io;
+use crate::HBuf;
+
+impl HBuf {
+
+    ///
+    /// Locks all pages of the memory of this HBuf up to the capacity into physical memory
+    /// so that they are never swapped to disk. This uses mlock on unix and VirtualLock on windows.
+    ///
+    /// This may fail if the process lacks the required privileges or exceeds RLIMIT_MEMLOCK on linux
+    /// or the minimum working set size on windows.
+    /// Locks are not reference counted by the OS, a single munlock unlocks the pages for every HBuf that shares them.
+    ///
+    pub fn mlock(&self) -> io::Result<()> {
+        if self.capacity() == 0 {
+            return Ok(());
+        }
+
+        unsafe { lock(self.as_ptr(), self.capacity()) }
+    }
+
+    ///
+    /// Unlocks all pages of the memory of this HBuf up to the capacity that were previously locked by mlock.
+    /// This uses munlock on unix and VirtualUnlock on windows.
+    ///
+    pub fn munlock(&self) -> io::Result<()> {
+        if self.capacity() == 0 {
+            return Ok(());
+        }
+
+        unsafe { unlock(self.as_ptr(), self.capacity()) }
+    }
+}
+
+#[cfg(unix)]
+unsafe fn lock(ptr: *mut u8, size: usize) -> io::Result<()> {
+    if libc::mlock(ptr.cast(), size) != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(crate) unsafe fn unlock(ptr: *mut u8, size: usize) -> io::Result<()> {
+    if libc::munlock(ptr.cast(), size) != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+unsafe fn lock(ptr: *mut u8, size: usize) -> io::Result<()> {
+    if windows_sys::Win32::System::Memory::VirtualLock(ptr.cast(), size) == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+#[cfg(windows)]
+pub(crate) unsafe fn unlock(ptr: *mut u8, size: usize) -> io::Result<()> {
+    if windows_sys::Win32::System::Memory::VirtualUnlock(ptr.cast(), size) == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
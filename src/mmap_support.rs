@@ -0,0 +1,62 @@
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+use memmap2::{MmapMut, MmapOptions};
+use crate::{DynDestructor, HBuf, HBufError};
+
+#[derive(Debug)]
+struct MmapDestructor {
+    mmap: Option<MmapMut>,
+    writable: bool
+}
+
+impl DynDestructor for MmapDestructor {
+    fn destroy(&mut self, _ptr: *mut u8, _size: usize) {
+        if let Some(mmap) = self.mmap.take() {
+            if self.writable {
+                //Nothing sensible can be done with the error here, the data is still in the page cache.
+                _ = mmap.flush();
+            }
+            drop(mmap);
+        }
+    }
+}
+
+impl HBuf {
+
+    ///
+    /// Maps the given file into memory. Capacity and limit are set to the length of the file.
+    /// The file is unmapped once no more references to the HBuf exist.
+    ///
+    /// If writable is true then changes to the buffer are written back to the file.
+    /// If writable is false then the file is mapped copy on write and changes to the buffer are never written back to the file.
+    ///
+    /// The mapping is page aligned.
+    /// Modifying or truncating the file while it is mapped (for example by another process) is undefined behavior.
+    ///
+    /// Returns an error if the file cannot be opened/mapped or is empty.
+    ///
+    pub fn mmap_file(path: &Path, writable: bool) -> io::Result<HBuf> {
+        let file = OpenOptions::new().read(true).write(writable).open(path)?;
+        if file.metadata()?.len() == 0 {
+            return Err(HBufError::ZeroSize.into());
+        }
+
+        let mut mmap = unsafe {
+            if writable {
+                MmapOptions::new().map_mut(&file)?
+            } else {
+                MmapOptions::new().map_copy(&file)?
+            }
+        };
+
+        let ptr = mmap.as_mut_ptr();
+        let size = mmap.len();
+        let destructor = MmapDestructor {
+            mmap: Some(mmap),
+            writable
+        };
+
+        Ok(unsafe { HBuf::from_raw_parts_with_dyn_destructor(ptr, size, Box::new(destructor)) })
+    }
+}
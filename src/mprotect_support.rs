@@ -0,0 +1,84 @@
+use std::io;
+use crate::HBuf;
+
+///
+/// Memory protection that can be applied to the memory of a HBuf with HBuf::protect.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protection {
+    ///
+    /// The memory can be read but writing to it raises SIGSEGV.
+    ///
+    ReadOnly,
+    ///
+    /// The memory can be read and written. This is the protection of all memory allocated by HBuf.
+    ///
+    ReadWrite,
+    ///
+    /// Any access to the memory raises SIGSEGV. This is useful for guard pages.
+    ///
+    NoAccess
+}
+
+impl Protection {
+    fn as_raw(self) -> libc::c_int {
+        match self {
+            Protection::ReadOnly => libc::PROT_READ,
+            Protection::ReadWrite => libc::PROT_READ | libc::PROT_WRITE,
+            Protection::NoAccess => libc::PROT_NONE
+        }
+    }
+}
+
+impl HBuf {
+
+    ///
+    /// Changes the protection of the memory of this HBuf up to the capacity using mprotect.
+    /// Accessing the memory in a way that violates the protection raises SIGSEGV, which terminates the process.
+    /// This includes accesses through other HBuf's that share the memory and through the methods of this HBuf.
+    ///
+    /// mprotect operates on whole pages, therefore the memory must start at a page boundary and the capacity
+    /// must be a multiple of the page size, otherwise an InvalidInput error is returned.
+    /// Use HBuf::allocate_page with a multiple of HBuf::page_size to allocate suitable memory.
+    ///
+    /// The pages are made readable and writable again before the memory is freed by its destructor.
+    /// Therefore the HBuf must have a destructor, otherwise an InvalidInput error is returned.
+    ///
+    pub fn protect(&self, protection: Protection) -> io::Result<()> {
+        let page_size = HBuf::page_size();
+        if !self.is_aligned_to(page_size) || self.capacity() % page_size != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "HBuf is not page aligned"));
+        }
+
+        if self.capacity() == 0 {
+            return Ok(());
+        }
+
+        let Some(destructor) = self.destructor_ref() else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "HBuf has no destructor"));
+        };
+
+        if protection != Protection::ReadWrite {
+            destructor.mark_protected();
+        }
+
+        if unsafe { libc::mprotect(self.as_ptr().cast(), self.capacity(), protection.as_raw()) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+}
+
+///
+/// Makes all pages that lie entirely within the given memory readable and writable again.
+/// Pages that only partially overlap the memory can never have been protected by HBuf::protect.
+///
+pub(crate) unsafe fn unprotect(ptr: *mut u8, size: usize) {
+    let page_size = HBuf::page_size();
+    let start = (ptr as usize).next_multiple_of(page_size);
+    let end = (ptr as usize + size) / page_size * page_size;
+    if start < end {
+        libc::mprotect(start as *mut libc::c_void, end - start, Protection::ReadWrite.as_raw());
+    }
+}
@@ -0,0 +1,156 @@
+use std::io;
+use std::ops::{Deref, DerefMut};
+use crate::HBuf;
+
+///
+/// Byte order used by OrderedHBuf to read and write values.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    ///
+    /// Least significant byte first.
+    ///
+    Little,
+    ///
+    /// Most significant byte first. This is the network byte order.
+    ///
+    Big,
+    ///
+    /// The byte order of the target.
+    ///
+    Native
+}
+
+impl ByteOrder {
+    fn is_big(self) -> bool {
+        match self {
+            ByteOrder::Little => false,
+            ByteOrder::Big => true,
+            ByteOrder::Native => cfg!(target_endian = "big")
+        }
+    }
+}
+
+///
+/// A HBuf with a configured byte order that is used by all read/write/get/set methods of this wrapper.
+/// All other methods of the HBuf are available through Deref.
+///
+#[derive(Debug, Clone)]
+pub struct OrderedHBuf {
+    buf: HBuf,
+    order: ByteOrder
+}
+
+macro_rules! ordered_type {
+    ($type:ty, $read_name:ident, $write_name:ident, $get_name:ident, $set_name:ident,
+     $read_le:ident, $read_be:ident, $write_le:ident, $write_be:ident, $get_le:ident, $get_be:ident, $set_le:ident, $set_be:ident) => {
+
+        ///
+        /// Reads a value in the configured byte order at the position and advances the position by its size.
+        /// Returns an UnexpectedEof error and leaves the position unchanged if less bytes remain before the limit.
+        ///
+        pub fn $read_name(&mut self) -> io::Result<$type> {
+            match self.order.is_big() {
+                true => self.buf.$read_be(),
+                false => self.buf.$read_le()
+            }
+        }
+
+        ///
+        /// Writes a value in the configured byte order at the position and advances the position by its size.
+        /// Returns an UnexpectedEof error and leaves the position unchanged if less bytes remain before the limit.
+        ///
+        pub fn $write_name(&mut self, value: $type) -> io::Result<()> {
+            match self.order.is_big() {
+                true => self.buf.$write_be(value),
+                false => self.buf.$write_le(value)
+            }
+        }
+
+        ///
+        /// Reads a value in the configured byte order at the given offset.
+        /// This does not use or modify the position.
+        /// panics on out of bounds.
+        ///
+        pub fn $get_name(&self, index: usize) -> $type {
+            match self.order.is_big() {
+                true => self.buf.$get_be(index),
+                false => self.buf.$get_le(index)
+            }
+        }
+
+        ///
+        /// Writes a value in the configured byte order at the given offset.
+        /// This does not use or modify the position.
+        /// panics on out of bounds.
+        ///
+        pub fn $set_name(&mut self, index: usize, value: $type) {
+            match self.order.is_big() {
+                true => self.buf.$set_be(index, value),
+                false => self.buf.$set_le(index, value)
+            }
+        }
+    };
+}
+
+impl HBuf {
+
+    ///
+    /// Turns this HBuf into an OrderedHBuf that reads and writes values in the given byte order.
+    ///
+    pub fn with_order(self, order: ByteOrder) -> OrderedHBuf {
+        OrderedHBuf {
+            buf: self,
+            order
+        }
+    }
+}
+
+impl OrderedHBuf {
+
+    ///
+    /// Returns the configured byte order.
+    ///
+    pub fn order(&self) -> ByteOrder {
+        self.order
+    }
+
+    ///
+    /// Changes the byte order used by all subsequent calls.
+    ///
+    pub fn set_order(&mut self, order: ByteOrder) {
+        self.order = order;
+    }
+
+    ///
+    /// Returns the HBuf.
+    ///
+    pub fn into_inner(self) -> HBuf {
+        self.buf
+    }
+
+    ordered_type!(u16, read_u16, write_u16, get_u16, set_u16, read_u16_le, read_u16_be, write_u16_le, write_u16_be, get_u16_le, get_u16_be, set_u16_le, set_u16_be);
+    ordered_type!(i16, read_i16, write_i16, get_i16, set_i16, read_i16_le, read_i16_be, write_i16_le, write_i16_be, get_i16_le, get_i16_be, set_i16_le, set_i16_be);
+    ordered_type!(u32, read_u32, write_u32, get_u32, set_u32, read_u32_le, read_u32_be, write_u32_le, write_u32_be, get_u32_le, get_u32_be, set_u32_le, set_u32_be);
+    ordered_type!(i32, read_i32, write_i32, get_i32, set_i32, read_i32_le, read_i32_be, write_i32_le, write_i32_be, get_i32_le, get_i32_be, set_i32_le, set_i32_be);
+    ordered_type!(u64, read_u64, write_u64, get_u64, set_u64, read_u64_le, read_u64_be, write_u64_le, write_u64_be, get_u64_le, get_u64_be, set_u64_le, set_u64_be);
+    ordered_type!(i64, read_i64, write_i64, get_i64, set_i64, read_i64_le, read_i64_be, write_i64_le, write_i64_be, get_i64_le, get_i64_be, set_i64_le, set_i64_be);
+    ordered_type!(u128, read_u128, write_u128, get_u128, set_u128, read_u128_le, read_u128_be, write_u128_le, write_u128_be, get_u128_le, get_u128_be, set_u128_le, set_u128_be);
+    ordered_type!(i128, read_i128, write_i128, get_i128, set_i128, read_i128_le, read_i128_be, write_i128_le, write_i128_be, get_i128_le, get_i128_be, set_i128_le, set_i128_be);
+    ordered_type!(f32, read_f32, write_f32, get_f32, set_f32, read_f32_le, read_f32_be, write_f32_le, write_f32_be, get_f32_le, get_f32_be, set_f32_le, set_f32_be);
+    ordered_type!(f64, read_f64, write_f64, get_f64, set_f64, read_f64_le, read_f64_be, write_f64_le, write_f64_be, get_f64_le, get_f64_be, set_f64_le, set_f64_be);
+}
+
+impl Deref for OrderedHBuf {
+    type Target = HBuf;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buf
+    }
+}
+
+impl DerefMut for OrderedHBuf {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buf
+    }
+}
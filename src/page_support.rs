@@ -0,0 +1,60 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::{HBuf, HBufError};
+
+static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+impl HBuf {
+
+    ///
+    /// Returns the page size of the OS. This uses sysconf(_SC_PAGESIZE) on unix and GetSystemInfo on windows.
+    /// The value is queried once and cached afterward.
+    ///
+    pub fn page_size() -> usize {
+        let cached = PAGE_SIZE.load(Ordering::Relaxed);
+        if cached != 0 {
+            return cached;
+        }
+
+        let page_size = query_page_size();
+        PAGE_SIZE.store(page_size, Ordering::Relaxed);
+        page_size
+    }
+
+    ///
+    /// Allocates memory that is aligned to the page size of the OS.
+    /// The contents of the memory are undefined.
+    ///
+    /// This function panics if the size is 0.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn allocate_page(size: usize) -> HBuf {
+        HBuf::allocate_aligned(size, HBuf::page_size())
+    }
+
+    ///
+    /// Allocates memory that is aligned to the page size of the OS.
+    /// The contents of the memory are undefined.
+    ///
+    pub fn try_allocate_page(size: usize) -> Result<HBuf, HBufError> {
+        HBuf::try_allocate_aligned(size, HBuf::page_size())
+    }
+}
+
+#[cfg(unix)]
+fn query_page_size() -> usize {
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if page_size <= 0 {
+        panic!("sysconf(_SC_PAGESIZE) failed: {}", std::io::Error::last_os_error());
+    }
+
+    page_size as usize
+}
+
+#[cfg(windows)]
+fn query_page_size() -> usize {
+    use windows_sys::Win32::System::SystemInformation::{GetSystemInfo, SYSTEM_INFO};
+
+    let mut info: SYSTEM_INFO = unsafe { std::mem::zeroed() };
+    unsafe { GetSystemInfo(&mut info) };
+    info.dwPageSize as usize
+}
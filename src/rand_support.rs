@@ -0,0 +1,12 @@
+use rand_core::RngCore;
+use crate::HBuf;
+
+impl HBuf {
+
+    ///
+    /// Fills all bytes up to the limit with random bytes from the given random number generator.
+    ///
+    pub fn fill_random(&mut self, rng: &mut impl RngCore) {
+        rng.fill_bytes(self.as_mut_slice());
+    }
+}
@@ -0,0 +1,144 @@
+use std::ops::Deref;
+use crate::HBuf;
+
+///
+/// A HBuf for sensitive data such as cryptographic keys whose clones never share memory.
+/// The memory is allocated by HBuf::allocate_secure and overwritten with zeros once it is dropped.
+/// With the mlock_support feature the memory is also locked into physical memory if the OS permits it
+/// and unlocked after it was overwritten. Locks are not reference counted by the OS, see HBuf::mlock.
+///
+/// Unlike HBuf, cloning a SecureHBuf allocates new memory and copies all bytes up to the capacity,
+/// so every clone is wiped independently when it is dropped.
+/// This makes clone as expensive as an allocation plus a copy, plus an mlock syscall with the mlock_support feature.
+///
+/// Read access to the HBuf is available through Deref. There is intentionally no DerefMut,
+/// since replacing or swapping the inner HBuf would move the secret memory out of the SecureHBuf.
+/// The methods that modify the memory, limit or position in place are available on SecureHBuf directly.
+///
+/// Methods of HBuf that create new handles to the same memory such as split or duplicate
+/// are still available through Deref and return regular HBufs that share the memory.
+/// The memory is only wiped and unlocked once the last of them is dropped.
+///
+#[derive(Debug)]
+pub struct SecureHBuf {
+    buf: HBuf,
+    locked: bool
+}
+
+impl SecureHBuf {
+
+    ///
+    /// Allocates the given amount of zeroed memory, see HBuf::allocate_secure.
+    /// With the mlock_support feature the memory is locked into physical memory if possible.
+    /// Failing to lock the memory is not an error, see is_locked.
+    ///
+    /// This function panics if the size is 0.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn allocate(size: usize) -> SecureHBuf {
+        let buf = HBuf::allocate_secure(size);
+
+        #[cfg(feature = "mlock_support")]
+        let locked = buf.mlock().is_ok();
+        #[cfg(feature = "mlock_support")]
+        if locked {
+            if let Some(destructor) = buf.destructor_ref() {
+                destructor.mark_locked();
+            }
+        }
+        #[cfg(not(feature = "mlock_support"))]
+        let locked = false;
+
+        SecureHBuf {
+            buf,
+            locked
+        }
+    }
+
+    ///
+    /// Allocates a new SecureHBuf with a capacity of data.len() and copies data into it.
+    ///
+    /// This function panics if data is empty.
+    /// This function panics/aborts if the amount of memory could not be allocated.
+    ///
+    pub fn from_slice(data: &[u8]) -> SecureHBuf {
+        let mut secure = SecureHBuf::allocate(data.len());
+        secure.buf.copy_from_slice(0, data);
+        secure
+    }
+
+    ///
+    /// Returns true if the memory is locked into physical memory.
+    /// This is always false without the mlock_support feature.
+    ///
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    ///
+    /// Returns a mutable slice that is backed by the memory, see HBuf::as_mut_slice.
+    ///
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        self.buf.as_mut_slice()
+    }
+
+    ///
+    /// Sets all bytes up to the limit to the given value, see HBuf::fill.
+    ///
+    pub fn fill(&mut self, value: u8) {
+        self.buf.fill(value);
+    }
+
+    ///
+    /// Copies all bytes from src into the memory starting at the given offset, see HBuf::copy_from_slice.
+    /// panics if offset+src.len() > limit.
+    ///
+    pub fn copy_from_slice(&mut self, offset: usize, src: &[u8]) {
+        self.buf.copy_from_slice(offset, src);
+    }
+
+    ///
+    /// Changes the limit, see HBuf::set_limit.
+    /// panics if limit > capacity.
+    ///
+    pub fn set_limit(&mut self, new_limit: usize) {
+        self.buf.set_limit(new_limit);
+    }
+
+    ///
+    /// Changes the position, see HBuf::set_position.
+    /// panics if position > limit.
+    ///
+    pub fn set_position(&mut self, new_position: usize) {
+        self.buf.set_position(new_position);
+    }
+
+    ///
+    /// Overwrites the entire capacity with zeros, see HBuf::zeroize.
+    ///
+    pub fn zeroize(&mut self) {
+        self.buf.zeroize();
+    }
+}
+
+///
+/// Allocates new memory and copies all bytes up to the capacity as well as the limit and position.
+/// The clone does not share memory with this SecureHBuf.
+///
+impl Clone for SecureHBuf {
+    fn clone(&self) -> Self {
+        let mut clone = SecureHBuf::allocate(self.buf.capacity());
+        unsafe { std::ptr::copy_nonoverlapping(self.buf.as_ptr(), clone.buf.as_ptr(), self.buf.capacity()) }
+        clone.buf.set_limit(self.buf.limit());
+        clone.buf.set_position(self.buf.position());
+        clone
+    }
+}
+
+impl Deref for SecureHBuf {
+    type Target = HBuf;
+
+    fn deref(&self) -> &Self::Target {
+        &self.buf
+    }
+}
@@ -0,0 +1,63 @@
+use std::fmt::Formatter;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::HBuf;
+
+///
+/// Serializes the bytes up to the limit of the HBuf.
+/// Metadata such as position, capacity, alignment or the destructor is not serialized.
+///
+impl Serialize for HBuf {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self.as_slice())
+    }
+}
+
+///
+/// Deserializes the bytes into a newly allocated HBuf.
+/// The limit and capacity are set to the amount of bytes and the position is 0.
+///
+impl<'de> Deserialize<'de> for HBuf {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_byte_buf(HBufVisitor)
+    }
+}
+
+struct HBufVisitor;
+
+impl HBufVisitor {
+    fn copy_of<E: Error>(data: &[u8]) -> Result<HBuf, E> {
+        if data.is_empty() {
+            return Ok(HBuf::empty());
+        }
+
+        let mut buf = HBuf::try_allocate(data.len()).map_err(E::custom)?;
+        buf.copy_from_slice(0, data);
+        Ok(buf)
+    }
+}
+
+impl<'de> Visitor<'de> for HBufVisitor {
+    type Value = HBuf;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a byte array")
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        HBufVisitor::copy_of(v)
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        HBufVisitor::copy_of(v.as_slice())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut data = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            data.push(byte);
+        }
+
+        HBufVisitor::copy_of(data.as_slice())
+    }
+}
@@ -0,0 +1,49 @@
+use std::io;
+use std::io::Read;
+use crate::HBuf;
+
+///
+/// Read adapter that reads at most a given amount of bytes from the position of a HBuf.
+/// Reading advances the position of the HBuf, once the amount is exhausted reads return 0 (EOF).
+/// This is similar to Read::take but borrows the HBuf, so it stays usable afterward.
+///
+#[derive(Debug)]
+pub struct TakeHBuf<'a> {
+    buf: &'a mut HBuf,
+    remaining: usize
+}
+
+impl HBuf {
+
+    ///
+    /// Returns a TakeHBuf that reads at most n bytes starting at the position of this HBuf.
+    /// This is not called take because Read::take, which consumes the HBuf, would take precedence
+    /// whenever the Read trait is in scope.
+    ///
+    pub fn take_bytes(&mut self, n: usize) -> TakeHBuf<'_> {
+        TakeHBuf {
+            buf: self,
+            remaining: n
+        }
+    }
+}
+
+impl TakeHBuf<'_> {
+
+    ///
+    /// Returns the amount of bytes that can still be read before EOF.
+    /// This is the smaller of the remaining amount and the bytes remaining before the limit of the HBuf.
+    ///
+    pub fn remaining(&self) -> usize {
+        self.remaining.min(self.buf.remaining())
+    }
+}
+
+impl Read for TakeHBuf<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let max = buf.len().min(self.remaining);
+        let read = self.buf.read(&mut buf[..max])?;
+        self.remaining -= read;
+        Ok(read)
+    }
+}
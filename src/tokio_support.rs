@@ -0,0 +1,38 @@
+use std::io;
+use std::io::Write;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use crate::HBuf;
+
+///
+/// Behaves exactly like the Read implementation. Since the data is in memory this is always ready.
+///
+impl AsyncRead for HBuf {
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let position = this.position();
+        let to_copy = buf.remaining().min(this.remaining());
+        buf.put_slice(&this.as_slice()[position..position+to_copy]);
+        this.set_position(position+to_copy);
+        Poll::Ready(Ok(()))
+    }
+}
+
+///
+/// Behaves exactly like the Write implementation. Since the data is in memory this is always ready.
+/// Flushing and shutting down is a noop.
+///
+impl AsyncWrite for HBuf {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
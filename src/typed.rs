@@ -0,0 +1,116 @@
+use std::mem::{align_of, size_of};
+use std::ops::{Index, IndexMut};
+use crate::HBuf;
+
+///
+/// A HBuf that is interpreted as a slice of elements of type T.
+/// The alignment and size of the memory are checked once when the TypedHBuf is created,
+/// so all accessors work without returning an Option.
+///
+/// The amount of elements is the limit of the HBuf divided by the size of T.
+///
+#[derive(Debug, Clone)]
+pub struct TypedHBuf<T: Copy> {
+    buf: HBuf,
+    len: usize,
+    _marker: std::marker::PhantomData<T>
+}
+
+impl HBuf {
+
+    ///
+    /// Turns this HBuf into a TypedHBuf of elements of type T.
+    /// Returns this HBuf unchanged if it is not aligned for T, if the limit is not a multiple of the size of T
+    /// or if T is zero sized.
+    ///
+    /// This function is unsafe because the caller must ensure that every bit pattern that may be stored in
+    /// the memory is a valid value of T.
+    ///
+    pub unsafe fn into_typed<T: Copy>(self) -> Result<TypedHBuf<T>, HBuf> {
+        if size_of::<T>() == 0 || !self.is_aligned_to(align_of::<T>()) || self.limit() % size_of::<T>() != 0 {
+            return Err(self);
+        }
+
+        let len = self.limit() / size_of::<T>();
+        Ok(TypedHBuf {
+            buf: self,
+            len,
+            _marker: std::marker::PhantomData
+        })
+    }
+}
+
+impl<T: Copy> TypedHBuf<T> {
+
+    ///
+    /// Returns the amount of elements.
+    ///
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    ///
+    /// Returns true if there are no elements.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///
+    /// Returns a copy of the element at the given index.
+    /// panics on out of bounds.
+    ///
+    pub fn get(&self, index: usize) -> T {
+        self.as_slice()[index]
+    }
+
+    ///
+    /// Sets the element at the given index to the value.
+    /// panics on out of bounds.
+    ///
+    pub fn set(&mut self, index: usize, value: T) {
+        self.as_mut_slice()[index] = value;
+    }
+
+    ///
+    /// Returns a slice of all elements.
+    ///
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.buf.as_ptr().cast::<T>(), self.len) }
+    }
+
+    ///
+    /// Returns a mutable slice of all elements.
+    ///
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.buf.as_ptr().cast::<T>(), self.len) }
+    }
+
+    ///
+    /// Returns a reference to the underlying HBuf.
+    ///
+    pub fn as_hbuf(&self) -> &HBuf {
+        &self.buf
+    }
+
+    ///
+    /// Returns the underlying HBuf.
+    ///
+    pub fn into_inner(self) -> HBuf {
+        self.buf
+    }
+}
+
+impl<T: Copy> Index<usize> for TypedHBuf<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.as_slice()[index]
+    }
+}
+
+impl<T: Copy> IndexMut<usize> for TypedHBuf<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.as_mut_slice()[index]
+    }
+}
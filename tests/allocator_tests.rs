@@ -0,0 +1,52 @@
+#![cfg(feature = "allocator_api")]
+#![feature(allocator_api)]
+
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::ptr::NonNull;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use heapbuf::HBuf;
+
+#[derive(Clone, Default)]
+struct CountingAllocator {
+    allocations: Arc<AtomicUsize>,
+    deallocations: Arc<AtomicUsize>
+}
+
+unsafe impl Allocator for CountingAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.allocations.fetch_add(1, Ordering::SeqCst);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.fetch_add(1, Ordering::SeqCst);
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn test_allocate_in_global() {
+    let mut buf = HBuf::allocate_in(64, 16, Global).unwrap();
+    assert!(buf.is_aligned_to(16));
+    assert_eq!(buf.capacity(), 64);
+    assert!(buf.has_destructor());
+    buf.fill(1);
+    assert_eq!(buf.iter().map(|b| *b as usize).sum::<usize>(), 64);
+}
+
+#[test]
+fn test_allocate_in_counting() {
+    let allocator = CountingAllocator::default();
+    let buf = HBuf::allocate_in(128, 8, allocator.clone()).unwrap();
+    assert_eq!(allocator.allocations.load(Ordering::SeqCst), 1);
+    let copy = buf.clone();
+    drop(buf);
+    assert_eq!(allocator.deallocations.load(Ordering::SeqCst), 0);
+    drop(copy);
+    assert_eq!(allocator.deallocations.load(Ordering::SeqCst), 1);
+
+    assert!(HBuf::allocate_in(0, 8, allocator.clone()).is_err());
+    assert!(HBuf::allocate_in(8, 3, allocator.clone()).is_err());
+    assert_eq!(allocator.allocations.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "base64_support")]
+
+use heapbuf::{Base64Alphabet, HBuf, HBufError};
+
+#[test]
+fn test_base64_round_trip() {
+    for len in 1..=6usize {
+        let mut buf = HBuf::allocate(len);
+        for x in 0..len {
+            buf[x] = (x as u8).wrapping_mul(0x3F).wrapping_add(0xF8);
+        }
+
+        for alphabet in [Base64Alphabet::Standard, Base64Alphabet::UrlSafe] {
+            let encoded = buf.to_base64(alphabet);
+            assert_eq!(encoded.len(), len.div_ceil(3) * 4);
+            let decoded = HBuf::from_base64(&encoded, alphabet).unwrap();
+            assert_eq!(decoded.as_slice(), buf.as_slice());
+
+            let unpadded = HBuf::from_base64(encoded.trim_end_matches('='), alphabet).unwrap();
+            assert_eq!(unpadded.as_slice(), buf.as_slice());
+        }
+    }
+}
+
+#[test]
+fn test_base64_padding() {
+    let buf = HBuf::from_hex_string("fbff").unwrap();
+    assert_eq!(buf.to_base64(Base64Alphabet::Standard), "+/8=");
+    assert_eq!(buf.to_base64(Base64Alphabet::UrlSafe), "-_8=");
+
+    let mut buf = HBuf::from_hex_string("666f6f626172").unwrap();
+    buf.set_limit(1);
+    assert_eq!(buf.to_base64(Base64Alphabet::Standard), "Zg==");
+    buf.set_limit(2);
+    assert_eq!(buf.to_base64(Base64Alphabet::Standard), "Zm8=");
+    buf.set_limit(3);
+    assert_eq!(buf.to_base64(Base64Alphabet::Standard), "Zm9v");
+}
+
+#[test]
+fn test_base64_invalid() {
+    assert!(matches!(HBuf::from_base64("-_8=", Base64Alphabet::Standard), Err(HBufError::InvalidBase64)));
+    assert!(matches!(HBuf::from_base64("+/8=", Base64Alphabet::UrlSafe), Err(HBufError::InvalidBase64)));
+    assert!(matches!(HBuf::from_base64("Z", Base64Alphabet::Standard), Err(HBufError::InvalidBase64)));
+    assert!(matches!(HBuf::from_base64("", Base64Alphabet::Standard), Err(HBufError::ZeroSize)));
+}
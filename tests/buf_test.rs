@@ -166,4 +166,1784 @@ Reference count: 1
                 .to_ascii_lowercase());
 
     return Ok(());
-}
\ No newline at end of file
+}
+#[test]
+fn test_binary() {
+    let mut buf = HBuf::allocate(2);
+    buf[0] = 0x01;
+    buf[1] = 0xFF;
+    assert_eq!(format!("{:b}", buf), "0000000111111111");
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn test_get_overflow() {
+    let buf = HBuf::allocate_zeroed(16);
+    buf.get_u32(usize::MAX);
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn test_get_generic_overflow() {
+    let buf = HBuf::allocate_zeroed(16);
+    unsafe { buf.get::<u64>(usize::MAX - 2); }
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn test_set_overflow() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    unsafe { buf.set(usize::MAX, 1u32); }
+}
+
+#[test]
+#[should_panic(expected = "overflows")]
+fn test_atomic_overflow() {
+    let buf = HBuf::allocate_aligned_zeroed(16, 4);
+    assert!(buf.as_atomic_u32(usize::MAX).is_none());
+    buf.atomic_load_u32(usize::MAX, Ordering::SeqCst);
+}
+
+#[test]
+fn test_range_index() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    for x in 0..32usize {
+        buf[x] = x as u8;
+    }
+    buf.set_limit(24);
+
+    assert_eq!(&buf[10..13], &[10, 11, 12]);
+    assert_eq!(buf[20..].len(), 4);
+    assert_eq!(&buf[..2], &[0, 1]);
+    assert_eq!(buf[..].len(), 24);
+    assert_eq!(&buf[5..=6], &[5, 6]);
+    assert_eq!(&buf[..=1], &[0, 1]);
+    assert_eq!(buf[24..].len(), 0);
+
+    buf[4..8].copy_from_slice(&[9, 9, 9, 9]);
+    assert_eq!(buf.get_u32(4), u32::from_ne_bytes([9, 9, 9, 9]));
+    buf[..].fill(1);
+    assert_eq!(buf[23], 1);
+}
+
+#[test]
+#[should_panic(expected = "Range end 25 is out of bounds for HBuf with limit 24")]
+fn test_range_index_limit() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.set_limit(24);
+    let _ = &buf[20..25];
+}
+
+#[test]
+#[should_panic(expected = "Range start 5 is greater than range end 4")]
+fn test_range_index_reversed() {
+    let buf = HBuf::allocate_zeroed(32);
+    #[allow(clippy::reversed_empty_ranges)]
+    let _ = &buf[5..4];
+}
+
+#[test]
+fn test_copy_slice() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.copy_from_slice(12, &[1, 2, 3, 4]);
+    assert_eq!(&buf[12..], &[1, 2, 3, 4]);
+    assert_eq!(buf.position(), 0);
+
+    let mut dst = [0u8; 4];
+    buf.copy_to_slice(12, &mut dst);
+    assert_eq!(dst, [1, 2, 3, 4]);
+
+    let mut dst = [0xFFu8; 16];
+    buf.copy_to_slice(0, &mut dst);
+    assert_eq!(&dst[..12], &[0u8; 12]);
+    buf.copy_from_slice(0, &[0xAA; 16]);
+    assert_eq!(buf.as_slice(), &[0xAA; 16]);
+    assert_eq!(buf.position(), 0);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_copy_from_slice_overflow() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.copy_from_slice(13, &[1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_copy_to_slice_overflow() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(8);
+    let mut dst = [0u8; 4];
+    buf.copy_to_slice(5, &mut dst);
+}
+
+#[test]
+fn test_copy_within() {
+    let mut buf = HBuf::allocate_zeroed(10);
+    for x in 0..10usize {
+        buf[x] = x as u8;
+    }
+
+    buf.copy_within(2..8, 4);
+    assert_eq!(buf.as_slice(), &[0, 1, 2, 3, 2, 3, 4, 5, 6, 7]);
+
+    buf.copy_within(4..10, 1);
+    assert_eq!(buf.as_slice(), &[0, 2, 3, 4, 5, 6, 7, 5, 6, 7]);
+
+    buf.copy_within(0..10, 0);
+    assert_eq!(buf.as_slice(), &[0, 2, 3, 4, 5, 6, 7, 5, 6, 7]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_copy_within_dest_overflow() {
+    let mut buf = HBuf::allocate_zeroed(10);
+    buf.copy_within(0..4, 7);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_copy_within_src_overflow() {
+    let mut buf = HBuf::allocate_zeroed(10);
+    buf.set_limit(6);
+    buf.copy_within(4..8, 0);
+}
+
+#[test]
+fn test_resize() -> std::io::Result<()> {
+    let mut buf = HBuf::try_allocate_aligned_zeroed(16, 8)?;
+    for x in 0..16usize {
+        buf[x] = x as u8;
+    }
+
+    buf.try_resize(64)?;
+    assert_eq!(buf.capacity(), 64);
+    assert_eq!(buf.limit(), 64);
+    assert_eq!(0, buf.as_ptr().align_offset(8));
+    for x in 0..16usize {
+        assert_eq!(buf[x], x as u8);
+    }
+
+    buf.set_limit(32);
+    buf.set_position(20);
+    buf.try_resize(24)?;
+    assert_eq!(buf.capacity(), 24);
+    assert_eq!(buf.limit(), 24);
+    assert_eq!(buf.position(), 20);
+    assert_eq!(buf[15], 15);
+
+    let shared = buf.clone();
+    assert!(matches!(buf.try_resize(128), Err(HBufError::Shared)));
+    let sub = shared.split(4, 4);
+    drop(shared);
+    drop(buf);
+    let mut sub = sub;
+    assert!(matches!(sub.try_resize(128), Err(HBufError::NotOwned)));
+
+    let mut x = vec![0u8; 16];
+    let mut raw = unsafe { HBuf::from_raw_parts(x.as_mut_ptr(), x.len()) };
+    assert!(matches!(raw.try_resize(128), Err(HBufError::NotOwned)));
+
+    fn noop(_: *mut u8, _: usize) {}
+    let mut raw = unsafe { HBuf::from_raw_parts_with_destructor(x.as_mut_ptr(), x.len(), noop) };
+    assert!(matches!(raw.try_resize(128), Err(HBufError::NotOwned)));
+    assert_eq!(raw.capacity(), 16);
+
+    return Ok(());
+}
+
+#[test]
+fn test_fill_range() {
+    let mut buf = HBuf::allocate_zeroed(30);
+    buf.fill_range(10..20, 0xAB);
+    assert_eq!(&buf[..10], &[0u8; 10]);
+    assert_eq!(&buf[10..20], &[0xABu8; 10]);
+    assert_eq!(&buf[20..], &[0u8; 10]);
+    buf.fill_range(30..30, 1);
+    assert_eq!(&buf[20..], &[0u8; 10]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_fill_range_overflow() {
+    let mut buf = HBuf::allocate_zeroed(30);
+    buf.set_limit(20);
+    buf.fill_range(10..21, 0xAB);
+}
+
+#[test]
+fn test_fill_pattern() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.set_limit(30);
+    buf.fill_pattern(2, &[1, 2, 3]);
+    assert_eq!(&buf[..2], &[0, 0]);
+    for x in 2..30usize {
+        assert_eq!(buf[x], [1, 2, 3][(x - 2) % 3]);
+    }
+    buf.reset();
+    assert_eq!(&buf[30..], &[0, 0]);
+
+    buf.fill_pattern(31, &[7, 8, 9]);
+    assert_eq!(buf[31], 7);
+    buf.fill_pattern(32, &[7, 8, 9]);
+}
+
+#[test]
+fn test_ct_eq() {
+    let mut a = HBuf::allocate_zeroed(32);
+    let mut b = HBuf::allocate_zeroed(32);
+    a.fill_pattern(0, &[1, 2, 3, 4]);
+    b.fill_pattern(0, &[1, 2, 3, 4]);
+    assert!(a.ct_eq(&b));
+    assert!(a.ct_eq_slice(b.as_slice()));
+
+    b[31] = 0;
+    assert!(!a.ct_eq(&b));
+    b[31] = 4;
+    b[0] = 0;
+    assert!(!a.ct_eq(&b));
+
+    b.set_limit(31);
+    assert!(!a.ct_eq(&b));
+    assert!(!a.ct_eq_slice(&[]));
+}
+
+#[test]
+fn test_find() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.copy_from_slice(0, b"GET / HTTP\r\n\r\nXY");
+    assert_eq!(buf.find_byte(b'\r'), Some(10));
+    assert_eq!(buf.rfind_byte(b'\r'), Some(12));
+    assert_eq!(buf.find_subslice(b"\r\n\r\n"), Some(10));
+    assert_eq!(buf.find_subslice(b""), Some(0));
+    assert_eq!(buf.find_byte(b'Z'), None);
+    assert_eq!(buf.find_subslice(b"HTTP/1.1"), None);
+
+    buf.set_position(11);
+    assert_eq!(buf.find_byte(b'\r'), Some(12));
+    assert_eq!(buf.find_byte(b'G'), None);
+    assert_eq!(buf.find_subslice(b"\r\n\r\n"), None);
+    assert_eq!(buf.find_subslice(b""), Some(11));
+
+    assert_eq!(buf.find_byte(b'Y'), Some(15));
+    assert_eq!(buf.find_subslice(b"XY"), Some(14));
+    buf.set_limit(15);
+    assert_eq!(buf.find_byte(b'Y'), None);
+    assert_eq!(buf.find_subslice(b"XY"), None);
+    assert_eq!(buf.rfind_byte(b'X'), Some(14));
+}
+
+#[test]
+fn test_swap_bytes() {
+    let mut buf = HBuf::allocate_aligned_zeroed(64, 8);
+    let values: Vec<u32> = (0..16u32).map(|x| x.wrapping_mul(0x01020304) ^ 0xA0B0C0D0).collect();
+    buf.as_mut_slice_u32().unwrap().copy_from_slice(values.as_slice());
+
+    buf.swap_bytes_u32();
+    for (idx, value) in values.iter().enumerate() {
+        assert_eq!(buf.as_slice_u32().unwrap()[idx], value.swap_bytes());
+    }
+
+    buf.swap_bytes_u32();
+    assert_eq!(buf.as_slice_u32().unwrap(), values.as_slice());
+
+    let expected: Vec<u64> = buf.as_slice_u64().unwrap().iter().map(|x| x.swap_bytes()).collect();
+    buf.swap_bytes_u64();
+    assert_eq!(buf.as_slice_u64().unwrap(), expected.as_slice());
+
+    let expected: Vec<u16> = buf.as_slice_u16().unwrap().iter().map(|x| x.swap_bytes()).collect();
+    buf.swap_bytes_u16();
+    assert_eq!(buf.as_slice_u16().unwrap(), expected.as_slice());
+}
+
+#[test]
+#[should_panic(expected = "not a multiple")]
+fn test_swap_bytes_length() {
+    let mut buf = HBuf::allocate_aligned_zeroed(64, 8);
+    buf.set_limit(30);
+    buf.swap_bytes_u32();
+}
+
+#[test]
+#[should_panic(expected = "not properly aligned")]
+fn test_swap_bytes_alignment() {
+    let buf = HBuf::allocate_aligned_zeroed(64, 8);
+    buf.split(1, 32).swap_bytes_u32();
+}
+
+#[test]
+fn test_bits() {
+    let mut buf = HBuf::allocate_zeroed(19);
+    buf.set_bit(0, true);
+    assert_eq!(buf[0], 0x80);
+    buf.set_bit(7, true);
+    assert_eq!(buf[0], 0x81);
+    assert!(buf.get_bit(0));
+    assert!(!buf.get_bit(1));
+    assert!(buf.get_bit(7));
+    buf.set_bit(0, false);
+    assert_eq!(buf[0], 0x01);
+    buf.toggle_bit(8);
+    assert_eq!(buf[1], 0x80);
+    buf.toggle_bit(8);
+    assert_eq!(buf[1], 0x00);
+    buf.set_bit(19 * 8 - 1, true);
+    assert_eq!(buf[18], 0x01);
+
+    assert_eq!(buf.count_ones(), 2);
+    buf.fill(0xFF);
+    assert_eq!(buf.count_ones(), 19 * 8);
+    buf.set_limit(9);
+    assert_eq!(buf.count_ones(), 9 * 8);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_bits_out_of_bounds() {
+    let mut buf = HBuf::allocate_zeroed(4);
+    buf.set_limit(2);
+    buf.get_bit(16);
+}
+
+#[test]
+fn test_atomic_fetch_add() {
+    let buf = HBuf::allocate_aligned_zeroed(64, 8);
+    let mut handles = Vec::new();
+    for _ in 0..8 {
+        let shared = buf.clone();
+        handles.push(thread::spawn(move || {
+            for _ in 0..1000 {
+                shared.atomic_fetch_add_u32(4, 1, Ordering::SeqCst);
+                shared.atomic_fetch_add_u64(8, 2, Ordering::Relaxed);
+            }
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(buf.atomic_load_u32(4, Ordering::SeqCst), 8000);
+    assert_eq!(buf.atomic_load_u64(8, Ordering::SeqCst), 16000);
+    assert_eq!(buf.atomic_load_u32(0, Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_atomic_fetch_ops() {
+    let buf = HBuf::allocate_aligned_zeroed(16, 8);
+    buf.atomic_store_u32(0, 0b1100, Ordering::SeqCst);
+    assert_eq!(buf.atomic_fetch_sub_u32(0, 4, Ordering::SeqCst), 0b1100);
+    assert_eq!(buf.atomic_fetch_and_u32(0, 0b0110, Ordering::SeqCst), 0b1000);
+    assert_eq!(buf.atomic_fetch_or_u32(0, 0b0011, Ordering::SeqCst), 0);
+    assert_eq!(buf.atomic_fetch_xor_u32(0, 0b0001, Ordering::SeqCst), 0b0011);
+    assert_eq!(buf.atomic_fetch_max_u32(0, 100, Ordering::SeqCst), 0b0010);
+    assert_eq!(buf.atomic_fetch_min_u32(0, 50, Ordering::SeqCst), 100);
+    assert_eq!(buf.atomic_load_u32(0, Ordering::SeqCst), 50);
+
+    assert_eq!(buf.atomic_fetch_sub_i8(8, 1, Ordering::SeqCst), 0);
+    assert_eq!(buf.atomic_fetch_min_i8(8, -5, Ordering::SeqCst), -1);
+    assert_eq!(buf.get_i8(8), -5);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_atomic_fetch_out_of_bounds() {
+    let buf = HBuf::allocate_aligned_zeroed(16, 8);
+    buf.atomic_fetch_add_u64(12, 1, Ordering::SeqCst);
+}
+
+#[test]
+fn test_atomic_fetch_update() {
+    let buf = HBuf::allocate_aligned_zeroed(16, 8);
+    let mut handles = Vec::new();
+    for _ in 0..2 {
+        let shared = buf.clone();
+        handles.push(thread::spawn(move || {
+            for _ in 0..1000 {
+                //Saturating increment that stops at 1500
+                let _ = shared.atomic_fetch_update_u32(4, Ordering::SeqCst, Ordering::SeqCst, |x| if x < 1500 { Some(x + 1) } else { None });
+            }
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(buf.atomic_load_u32(4, Ordering::SeqCst), 1500);
+    assert_eq!(buf.atomic_fetch_update_u32(4, Ordering::SeqCst, Ordering::SeqCst, |_| None), Err(1500));
+    assert_eq!(buf.atomic_fetch_update_u32(4, Ordering::SeqCst, Ordering::SeqCst, |x| Some(x * 2)), Ok(1500));
+    assert_eq!(buf.atomic_load_u32(4, Ordering::SeqCst), 3000);
+}
+
+#[test]
+fn test_deep_clone() {
+    let mut buf = HBuf::allocate_aligned_zeroed(64, 32);
+    buf.fill_pattern(0, &[1, 2, 3]);
+    buf.set_limit(40);
+    buf.set_position(7);
+
+    let mut copy = buf.deep_clone();
+    assert_eq!(copy.ref_count(), 1);
+    assert_eq!(buf.ref_count(), 1);
+    assert_ne!(copy.as_ptr(), buf.as_ptr());
+    assert_eq!(0, copy.as_ptr().align_offset(32));
+    assert_eq!(copy.capacity(), 64);
+    assert_eq!(copy.limit(), 40);
+    assert_eq!(copy.position(), 7);
+    assert_eq!(copy.as_slice(), buf.as_slice());
+
+    copy[0] = 0xFF;
+    copy.fill_range(1..40, 0xEE);
+    assert_eq!(buf[0], 1);
+    assert_eq!(buf[1], 2);
+
+    let sub = buf.split(3, 6).deep_clone();
+    assert_eq!(sub.as_slice(), &[1, 2, 3, 1, 2, 3]);
+    assert_eq!(buf.ref_count(), 1);
+}
+
+#[test]
+fn test_make_mut() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    assert!(buf.is_unique());
+    let ptr = buf.as_ptr();
+    buf.make_mut()[0] = 1;
+    assert_eq!(ptr, buf.as_ptr());
+
+    let other = buf.clone();
+    assert!(!buf.is_unique());
+    assert!(!other.is_unique());
+
+    buf.make_mut()[0] = 2;
+    assert_ne!(ptr, buf.as_ptr());
+    assert!(buf.is_unique());
+    assert!(other.is_unique());
+    assert_eq!(buf[0], 2);
+    assert_eq!(other[0], 1);
+
+    let mut sub = other.split(4, 4);
+    sub.make_mut()[0] = 3;
+    assert_eq!(other[4], 0);
+    assert_eq!(sub[0], 3);
+}
+
+fn sum_bytes(data: impl AsRef<[u8]>) -> u64 {
+    data.as_ref().iter().map(|b| *b as u64).sum()
+}
+
+fn fill_bytes(mut data: impl AsMut<[u8]>, value: u8) {
+    data.as_mut().fill(value);
+}
+
+#[test]
+fn test_as_ref() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.set_limit(4);
+    fill_bytes(&mut buf, 3);
+    assert_eq!(sum_bytes(&buf), 12);
+    buf.set_limit(8);
+    assert_eq!(sum_bytes(buf.clone()), 12);
+    assert_eq!(buf.as_ref(), &[3, 3, 3, 3, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_borrow_key() {
+    let mut map = std::collections::HashMap::new();
+    let mut key = HBuf::allocate_zeroed(8);
+    key.set_limit(3);
+    key.copy_from_slice(0, &[1, 2, 3]);
+    map.insert(key.clone(), 5);
+
+    let lookup: &[u8] = &[1, 2, 3];
+    assert_eq!(map.get(lookup), Some(&5));
+    assert_eq!(map.get(&[1u8, 2][..]), None);
+    assert_eq!(map.get(&key), Some(&5));
+}
+
+#[test]
+fn test_vec_round_trip() {
+    let mut vec = Vec::with_capacity(32);
+    vec.extend_from_slice(&[1, 2, 3, 4]);
+    let ptr = vec.as_ptr();
+
+    let mut buf = HBuf::from(vec);
+    assert_eq!(buf.as_ptr(), ptr as *mut u8);
+    assert_eq!(buf.capacity(), 32);
+    assert_eq!(buf.limit(), 4);
+    assert_eq!(buf.as_slice(), &[1, 2, 3, 4]);
+    assert!(buf.has_destructor());
+
+    buf.set_limit(6);
+    buf.copy_from_slice(4, &[5, 6]);
+
+    let vec: Vec<u8> = buf.try_into().unwrap();
+    assert_eq!(vec.as_ptr(), ptr);
+    assert_eq!(vec.capacity(), 32);
+    assert_eq!(vec, vec![1, 2, 3, 4, 5, 6]);
+
+    let empty = HBuf::from(Vec::new());
+    assert_eq!(empty.capacity(), 0);
+    let vec: Vec<u8> = empty.try_into().unwrap();
+    assert!(vec.is_empty());
+}
+
+#[test]
+fn test_vec_try_into_errors() {
+    let buf = HBuf::from(vec![1u8, 2, 3]);
+    let other = buf.clone();
+    assert!(matches!(Vec::<u8>::try_from(buf), Err(HBufError::Shared)));
+    drop(other.clone());
+
+    let split = other.split(1, 2);
+    drop(other);
+    assert!(matches!(Vec::<u8>::try_from(split), Err(HBufError::NotOwned)));
+
+    let allocated = HBuf::allocate(8);
+    assert!(matches!(Vec::<u8>::try_from(allocated), Err(HBufError::NotOwned)));
+}
+
+#[test]
+fn test_boxed_slice_round_trip() {
+    let mut boxed: Box<[u8; 32]> = Box::new([0u8; 32]);
+    boxed[5] = 5;
+    let ptr = boxed.as_ptr();
+
+    let buf = HBuf::from(boxed as Box<[u8]>);
+    assert_eq!(buf.as_ptr(), ptr as *mut u8);
+    assert_eq!(buf.capacity(), 32);
+    assert_eq!(buf.limit(), 32);
+    assert_eq!(buf[5], 5);
+
+    let other = buf.clone();
+    let buf = buf.into_boxed_slice().unwrap_err();
+    drop(other);
+
+    let boxed = buf.into_boxed_slice().unwrap();
+    assert_eq!(boxed.as_ptr(), ptr);
+    assert_eq!(boxed.len(), 32);
+    assert_eq!(boxed[5], 5);
+
+    let mut buf = HBuf::from(boxed);
+    buf.set_limit(8);
+    let boxed = buf.into_boxed_slice().unwrap();
+    assert_eq!(&*boxed, &[0, 0, 0, 0, 0, 5, 0, 0]);
+
+    let buf = HBuf::allocate(32);
+    let buf = buf.into_boxed_slice().unwrap_err();
+    assert_eq!(buf.capacity(), 32);
+}
+
+#[test]
+fn test_into_iter() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(5);
+    for (i, b) in (&mut buf).into_iter().enumerate() {
+        *b = i as u8 + 1;
+    }
+
+    let mut sum = 0u32;
+    for b in &buf {
+        sum += *b as u32;
+    }
+    assert_eq!(sum, 15);
+
+    let iter = buf.clone().into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(buf.ref_count(), 2);
+    assert_eq!(iter.map(|b| b as u32).sum::<u32>(), 15);
+    assert_eq!(buf.ref_count(), 1);
+
+    let mut iter = buf.into_iter();
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.as_slice(), &[2, 3, 4]);
+    assert_eq!(iter.collect::<Vec<u8>>(), vec![2, 3, 4]);
+}
+
+#[test]
+fn test_chunks() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(10);
+    for (i, chunk) in buf.chunks_mut(4).enumerate() {
+        chunk.fill(i as u8 + 1);
+    }
+
+    let chunks: Vec<&[u8]> = buf.chunks(4).collect();
+    assert_eq!(chunks, vec![&[1u8, 1, 1, 1][..], &[2, 2, 2, 2][..], &[3, 3][..]]);
+
+    let exact = buf.chunks_exact(4);
+    assert_eq!(exact.remainder(), &[3, 3]);
+    assert_eq!(exact.count(), 2);
+
+    let mut exact = buf.chunks_exact_mut(4);
+    exact.next().unwrap()[0] = 9;
+    assert_eq!(exact.into_remainder(), &mut [3, 3]);
+    assert_eq!(buf[0], 9);
+    buf.set_limit(16);
+    assert_eq!(buf[10], 0);
+}
+
+#[test]
+fn test_split_at() {
+    let buf = HBuf::allocate_zeroed(16);
+    let (mut header, mut body) = buf.split_at(4);
+    assert_eq!(buf.ref_count(), 3);
+    assert_eq!(header.capacity(), 4);
+    assert_eq!(body.capacity(), 12);
+
+    header.fill(0xAA);
+    body.fill(0xBB);
+    assert_eq!(&buf[0..4], &[0xAA; 4]);
+    assert_eq!(&buf[4..16], &[0xBB; 12]);
+
+    let (empty, all) = buf.split_at(0);
+    assert_eq!(empty.capacity(), 0);
+    assert_eq!(all.capacity(), 16);
+    let (all, empty) = buf.split_at(16);
+    assert_eq!(all.capacity(), 16);
+    assert_eq!(empty.capacity(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_split_at_out_of_bounds() {
+    let buf = HBuf::allocate_zeroed(16);
+    let _ = buf.split_at(17);
+}
+
+#[test]
+fn test_concat() {
+    let a = HBuf::from(vec![1u8, 2]);
+    let mut b = HBuf::from(vec![3u8, 4, 5, 6]);
+    b.set_limit(3);
+    let c = HBuf::from(vec![7u8]);
+
+    let all = HBuf::concat(&[&a, &b, &c]);
+    assert_eq!(all.capacity(), 6);
+    assert_eq!(all.as_slice(), &[1, 2, 3, 4, 5, 7]);
+    assert_eq!(all.ref_count(), 1);
+}
+
+#[test]
+fn test_append() {
+    let mut buf = HBuf::allocate_zeroed(4);
+    buf.set_limit(2);
+    buf.append(&HBuf::from(vec![1u8, 2])).unwrap();
+    assert_eq!(buf.capacity(), 4);
+    assert_eq!(buf.as_slice(), &[0, 0, 1, 2]);
+
+    buf.append(&HBuf::from(vec![3u8, 4, 5])).unwrap();
+    assert_eq!(buf.capacity(), 7);
+    assert_eq!(buf.as_slice(), &[0, 0, 1, 2, 3, 4, 5]);
+
+    let copy = buf.clone();
+    assert!(matches!(buf.append(&copy), Err(HBufError::Shared)));
+    assert_eq!(buf.limit(), 7);
+    drop(copy);
+
+    let own = buf.deep_clone();
+    buf.append(&own).unwrap();
+    assert_eq!(buf.as_slice(), &[0, 0, 1, 2, 3, 4, 5, 0, 0, 1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_push() {
+    let mut buf = HBuf::allocate(8);
+    buf.set_limit(0);
+    for x in 0..1000usize {
+        buf.push(x as u8).unwrap();
+    }
+
+    assert_eq!(buf.limit(), 1000);
+    assert_eq!(buf.capacity(), 1024);
+    for x in 0..1000usize {
+        assert_eq!(buf[x], x as u8);
+    }
+
+    buf.reserve(24).unwrap();
+    assert_eq!(buf.capacity(), 1024);
+    buf.reserve(25).unwrap();
+    assert_eq!(buf.capacity(), 2048);
+    assert_eq!(buf.limit(), 1000);
+
+    let copy = buf.clone();
+    assert!(matches!(buf.reserve(2000), Err(HBufError::Shared)));
+    drop(copy);
+
+    let mut sub = buf.split(0, 4);
+    drop(buf);
+    sub.set_limit(0);
+    sub.push(1).unwrap();
+    assert!(matches!(sub.reserve(4), Err(HBufError::NotOwned)));
+}
+
+#[test]
+fn test_push_empty() {
+    let mut buf = HBuf::empty();
+    buf.push(1).unwrap();
+    assert_eq!(buf.capacity(), 8);
+    assert_eq!(buf.limit(), 1);
+    assert!(buf.has_destructor());
+    for x in 2..=20u8 {
+        buf.push(x).unwrap();
+    }
+    assert_eq!(buf.as_slice(), (1..=20u8).collect::<Vec<_>>().as_slice());
+
+    let mut buf = HBuf::from_slice(&[]);
+    buf.reserve(100).unwrap();
+    assert_eq!(buf.capacity(), 100);
+    assert_eq!(buf.limit(), 0);
+}
+
+#[test]
+fn test_hexdump() {
+    let mut buf = HBuf::allocate_zeroed(12);
+    buf.copy_from_slice(0, b"Hello World!");
+    buf[5] = 0;
+
+    let opts = HexDumpOptions { bytes_per_row: 8, relative_offsets: true, ascii: true };
+    assert_eq!(buf.hexdump(opts), "\
+0x00000000: 4865 6c6c 6f00 576f  Hello.Wo\n\
+0x00000008: 726c 6421            rld!    \n");
+
+    let opts = HexDumpOptions { bytes_per_row: 8, relative_offsets: true, ascii: false };
+    assert_eq!(buf.hexdump(opts), "\
+0x00000000: 4865 6c6c 6f00 576f\n\
+0x00000008: 726c 6421\n");
+
+    let absolute = buf.hexdump(HexDumpOptions::default());
+    assert!(absolute.starts_with(&format!("0x{:0width$x}:", buf.as_ptr() as usize, width = (usize::BITS / 4) as usize)));
+    assert!(format!("{}", buf).contains(&absolute));
+}
+
+#[test]
+fn test_hex_string() {
+    let mut buf = HBuf::from_hex_string("00 01 7f80\n  FFab").unwrap();
+    assert_eq!(buf.as_slice(), &[0x00, 0x01, 0x7F, 0x80, 0xFF, 0xAB]);
+    assert_eq!(buf.to_hex_string(), "00017f80ffab");
+    assert_eq!(HBuf::from_hex_string(&buf.to_hex_string()).unwrap().as_slice(), buf.as_slice());
+
+    buf.set_limit(2);
+    assert_eq!(buf.to_hex_string(), "0001");
+    buf.set_limit(0);
+    assert_eq!(buf.to_hex_string(), "");
+
+    assert!(matches!(HBuf::from_hex_string("abc"), Err(HBufError::InvalidHex)));
+    assert!(matches!(HBuf::from_hex_string("a b c"), Err(HBufError::InvalidHex)));
+    assert!(matches!(HBuf::from_hex_string("0g"), Err(HBufError::InvalidHex)));
+    assert!(matches!(HBuf::from_hex_string("0x00"), Err(HBufError::InvalidHex)));
+    assert!(matches!(HBuf::from_hex_string(" "), Err(HBufError::ZeroSize)));
+}
+
+#[test]
+fn test_hex_format_limit() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.copy_from_slice(0, &[0xAB, 0xCD, 0xEF, 0x01, 0x23]);
+    buf.set_limit(4);
+    assert_eq!(format!("{:x}", buf), "abcdef01");
+    assert_eq!(format!("{:X}", buf), "ABCDEF01");
+    assert_eq!(format!("{:.2x}", buf), "abcd");
+    assert_eq!(format!("{:.8X}", buf), "ABCDEF01");
+    assert_eq!(format!("{:.0x}", buf), "");
+    assert_eq!(format!("{:12x}", buf), "    abcdef01");
+    assert_eq!(format!("{:-<12.1X}|", buf), "AB----------|");
+    assert_eq!(format!("{:^6.1x}", buf), "  ab  ");
+    assert_eq!(format!("{:4x}", buf), "abcdef01");
+}
+
+#[test]
+fn test_alignment() {
+    for alignment in [1usize, 16, 4096] {
+        let buf = HBuf::allocate_aligned(64, alignment);
+        assert!(buf.is_aligned_to(1));
+        assert!(buf.is_aligned_to(alignment));
+        assert!(buf.max_alignment() >= alignment);
+        assert!(buf.max_alignment().is_power_of_two());
+        assert!(buf.is_aligned_to(buf.max_alignment()));
+        assert!(!buf.is_aligned_to(buf.max_alignment() * 2));
+        assert_eq!(buf.as_slice_u32().is_some(), buf.is_aligned_to(4));
+    }
+
+    let buf = HBuf::allocate_aligned(64, 16);
+    let sub = buf.split(4, 8);
+    assert_eq!(sub.max_alignment(), 4);
+    assert!(sub.is_aligned_to(4));
+    assert!(!sub.is_aligned_to(8));
+}
+
+#[test]
+fn test_realign() {
+    let mut buf = HBuf::allocate_aligned_zeroed(32, 4);
+    buf.copy_from_slice(1, &1u32.to_ne_bytes());
+    buf.copy_from_slice(5, &2u32.to_ne_bytes());
+
+    let mut sub = buf.split(1, 9);
+    sub.set_limit(8);
+    assert!(sub.as_slice_u32().is_none());
+
+    let aligned = sub.realign(4);
+    assert!(aligned.is_aligned_to(4));
+    assert_eq!(aligned.capacity(), 8);
+    assert_eq!(aligned.ref_count(), 1);
+    assert_eq!(aligned.as_slice_u32().unwrap(), &[1, 2]);
+
+    let same = aligned.realign(4);
+    assert_eq!(same.as_ptr(), aligned.as_ptr());
+    assert_eq!(aligned.ref_count(), 2);
+}
+
+#[test]
+fn test_fill() {
+    let mut buf = HBuf::allocate_zeroed(64);
+    buf.set_limit(60);
+    buf.fill(0xAB);
+    assert!(buf.iter().all(|b| *b == 0xAB));
+    buf.set_limit(64);
+    assert_eq!(&buf[59..64], &[0xAB, 0, 0, 0, 0]);
+
+    let buf = HBuf::allocate_zeroed(4096);
+    assert!(buf.iter().all(|b| *b == 0));
+}
+
+#[test]
+fn test_allocate_zeroed_large() {
+    let size = 16 * 1024 * 1024;
+    let buf = HBuf::allocate_aligned_zeroed(size, 4096);
+    assert!(buf.is_aligned_to(4096));
+    assert_eq!(buf.count_ones(), 0);
+
+    let buf = HBuf::try_allocate_zeroed(size).unwrap();
+    assert_eq!(buf.count_ones(), 0);
+
+    let buf = HBuf::try_allocate_aligned_zeroed(size, 64).unwrap();
+    assert!(buf.is_aligned_to(64));
+    assert_eq!(buf.count_ones(), 0);
+}
+
+#[test]
+fn test_volatile() {
+    let mut buf = HBuf::allocate_aligned_zeroed(16, 8);
+    buf.write_volatile_u32(4, 0xDEADBEEF);
+    assert_eq!(buf.read_volatile_u32(4), 0xDEADBEEF);
+    buf.write_volatile_u8(1, 7);
+    assert_eq!(buf.read_volatile_u8(1), 7);
+    assert_eq!(buf[1], 7);
+
+    unsafe {
+        buf.write_volatile::<u64>(8, u64::MAX);
+        assert_eq!(buf.read_volatile::<u64>(8), u64::MAX);
+        assert_eq!(buf.read_volatile::<u32>(4), 0xDEADBEEF);
+    }
+}
+
+#[test]
+#[should_panic(expected = "not properly aligned")]
+fn test_volatile_unaligned() {
+    let buf = HBuf::allocate_aligned_zeroed(16, 8);
+    buf.read_volatile_u32(2);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_volatile_out_of_bounds() {
+    let mut buf = HBuf::allocate_aligned_zeroed(16, 8);
+    buf.write_volatile_u32(16, 1);
+}
+
+#[test]
+fn test_rotate() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.copy_from_slice(0, &[1, 2, 3, 4, 5, 6]);
+    buf.set_limit(5);
+    buf.rotate_left(2);
+    assert_eq!(buf.as_slice(), &[3, 4, 5, 1, 2]);
+    buf.rotate_right(2);
+    assert_eq!(buf.as_slice(), &[1, 2, 3, 4, 5]);
+    buf.rotate_left(5);
+    buf.rotate_right(0);
+    assert_eq!(buf.as_slice(), &[1, 2, 3, 4, 5]);
+    buf.set_limit(8);
+    assert_eq!(buf[5], 6);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_rotate_out_of_bounds() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.set_limit(5);
+    buf.rotate_right(6);
+}
+
+#[test]
+fn test_reverse() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.copy_from_slice(0, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    buf.set_limit(6);
+    buf.reverse();
+    assert_eq!(buf.as_slice(), &[6, 5, 4, 3, 2, 1]);
+
+    buf.reverse_range(1..4);
+    assert_eq!(buf.as_slice(), &[6, 3, 4, 5, 2, 1]);
+    buf.reverse_range(2..2);
+    assert_eq!(buf.as_slice(), &[6, 3, 4, 5, 2, 1]);
+
+    buf.set_limit(8);
+    assert_eq!(&buf[6..8], &[7, 8]);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_reverse_range_out_of_bounds() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.set_limit(4);
+    buf.reverse_range(2..5);
+}
+
+#[test]
+fn test_bitwise_with() {
+    let mut buf = HBuf::allocate(19);
+    for x in 0..19 {
+        buf[x] = x as u8;
+    }
+    let original = buf.deep_clone();
+    let key: Vec<u8> = (0..19u8).map(|x| x.wrapping_mul(37) ^ 0x5C).collect();
+
+    buf.xor_with(&key);
+    assert_ne!(buf.as_slice(), original.as_slice());
+    for x in 0..19 {
+        assert_eq!(buf[x], original[x] ^ key[x]);
+    }
+    buf.xor_with(&key);
+    assert_eq!(buf.as_slice(), original.as_slice());
+
+    buf.or_with(&[0xF0; 3]);
+    assert_eq!(&buf[0..4], &[0xF0, 0xF1, 0xF2, 3]);
+    buf.and_with(&[0x0F; 10]);
+    assert_eq!(&buf[0..11], &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+
+    buf.set_limit(2);
+    buf.xor_with(&[0xFF; 19]);
+    buf.set_limit(19);
+    assert_eq!(&buf[0..3], &[0xFF, 0xFE, 2]);
+}
+
+#[test]
+fn test_histogram() {
+    let mut buf = HBuf::allocate_zeroed(512);
+    for x in 0..256 {
+        buf[x] = x as u8;
+    }
+    buf.fill_range(256..512, 7);
+
+    assert_eq!(buf.count_byte(7), 257);
+    assert_eq!(buf.count_byte(0), 1);
+    let histogram = buf.byte_histogram();
+    assert_eq!(histogram[7], 257);
+    assert_eq!(histogram[255], 1);
+    assert_eq!(histogram.iter().sum::<usize>(), 512);
+
+    buf.set_limit(256);
+    assert_eq!(buf.count_byte(7), 1);
+    assert!((buf.shannon_entropy() - 8.0).abs() < 1e-9);
+
+    buf.set_limit(512);
+    buf.fill(3);
+    assert_eq!(buf.shannon_entropy(), 0.0);
+
+    buf.fill_pattern(0, &[1, 2]);
+    assert!((buf.shannon_entropy() - 1.0).abs() < 1e-9);
+
+    buf.set_limit(0);
+    assert_eq!(buf.shannon_entropy(), 0.0);
+    assert_eq!(buf.count_byte(1), 0);
+}
+
+#[test]
+fn test_fill_with() {
+    let mut buf = HBuf::allocate_zeroed(300);
+    buf.fill_with(|index| index as u8);
+    for x in 0..300 {
+        assert_eq!(buf[x], x as u8);
+    }
+
+    let mut calls = 0;
+    buf.set_limit(10);
+    buf.fill_with(|index| {
+        calls += 1;
+        (index * 2) as u8
+    });
+    assert_eq!(calls, 10);
+    assert_eq!(buf[9], 18);
+    buf.set_limit(11);
+    assert_eq!(buf[10], 10);
+}
+
+#[test]
+fn test_swap() {
+    let mut a = HBuf::from(vec![1u8, 2, 3]);
+    let mut b = HBuf::allocate_zeroed(8);
+    b.set_position(4);
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+
+    a.swap(&mut b);
+    assert_eq!(a.as_ptr(), b_ptr);
+    assert_eq!(b.as_ptr(), a_ptr);
+    assert_eq!(a.capacity(), 8);
+    assert_eq!(a.position(), 4);
+    assert_eq!(b.as_slice(), &[1, 2, 3]);
+}
+
+#[test]
+fn test_swap_bytes_with() {
+    let mut a = HBuf::from(vec![1u8, 2, 3]);
+    let mut b = HBuf::from(vec![4u8, 5, 6, 7]);
+    b.set_limit(3);
+    let a_ptr = a.as_ptr();
+
+    a.swap_bytes_with(&mut b);
+    assert_eq!(a.as_ptr(), a_ptr);
+    assert_eq!(a.as_slice(), &[4, 5, 6]);
+    assert_eq!(b.as_slice(), &[1, 2, 3]);
+    b.set_limit(4);
+    assert_eq!(b[3], 7);
+
+    let mut c = a.clone();
+    a.swap_bytes_with(&mut c);
+    assert_eq!(a.as_slice(), &[4, 5, 6]);
+
+    let mut d = a.split(0, 2);
+    let mut e = a.split(1, 2);
+    d.swap_bytes_with(&mut e);
+    assert_eq!(a.as_slice(), &[5, 6, 4]);
+}
+
+#[test]
+#[should_panic]
+fn test_swap_bytes_with_different_limit() {
+    let mut a = HBuf::allocate_zeroed(3);
+    let mut b = HBuf::allocate_zeroed(4);
+    a.swap_bytes_with(&mut b);
+}
+
+#[test]
+fn test_prefetch() {
+    let mut buf = HBuf::allocate_zeroed(4096);
+    for index in (0..4096).step_by(64) {
+        buf.prefetch_read(index);
+        buf.prefetch_write(index);
+    }
+    buf.prefetch_read(4096);
+    buf.prefetch_write(usize::MAX);
+    buf.fill(1);
+    assert_eq!(buf.count_byte(1), 4096);
+}
+
+#[test]
+fn test_typed() {
+    let buf = HBuf::allocate_aligned_zeroed(128 * 4, 4);
+    let mut typed = unsafe { buf.into_typed::<u32>() }.unwrap();
+    assert_eq!(typed.len(), 128);
+    for x in 0..128 {
+        typed.set(x, x as u32 * 3);
+    }
+    assert_eq!(typed.get(5), 15);
+    assert_eq!(typed[127], 381);
+    typed[0] = 7;
+    assert_eq!(typed.as_slice()[0], 7);
+    typed.as_mut_slice()[1] = 8;
+
+    let buf = typed.into_inner();
+    assert_eq!(buf.get_u32(4), 8);
+
+    let unaligned = buf.split(1, 8);
+    let unaligned = unsafe { unaligned.into_typed::<u32>() }.unwrap_err();
+    assert_eq!(unaligned.capacity(), 8);
+
+    let mut odd = buf.split(0, 8);
+    odd.set_limit(6);
+    assert!(unsafe { odd.into_typed::<u32>() }.is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_typed_out_of_bounds() {
+    let buf = HBuf::allocate_aligned_zeroed(16, 8);
+    let typed = unsafe { buf.into_typed::<u64>() }.unwrap();
+    typed.get(2);
+}
+
+#[test]
+fn test_atomic_limit() {
+    let mut buf = HBuf::allocate_zeroed(64);
+    buf.set_limit(0);
+    assert!(!buf.has_atomic_limit());
+    assert_eq!(buf.atomic_limit(Ordering::Acquire), 0);
+    buf.enable_atomic_limit();
+    assert!(buf.has_atomic_limit());
+    assert!(!buf.split(0, 8).has_atomic_limit());
+
+    let mut consumer = buf.clone();
+    let producer = thread::spawn(move || {
+        buf.set_limit(64);
+        buf[..32].fill(7);
+        buf.atomic_set_limit(32, Ordering::Release);
+    });
+
+    while consumer.atomic_limit(Ordering::Acquire) != 32 {
+        std::hint::spin_loop();
+    }
+
+    producer.join().unwrap();
+    assert_eq!(consumer.len(), 0);
+    assert_eq!(consumer.sync_limit(Ordering::Acquire), 32);
+    assert_eq!(consumer.len(), 32);
+    assert!(consumer.iter().all(|b| *b == 7));
+}
+
+#[test]
+fn test_atomic_limit_shrink() {
+    let mut buf = HBuf::allocate_zeroed(64);
+    buf.enable_atomic_limit();
+    buf.atomic_set_limit(48, Ordering::Release);
+    buf.set_limit(16);
+    buf.shrink_to_fit().unwrap();
+    assert_eq!(buf.capacity(), 16);
+    assert_eq!(buf.atomic_limit(Ordering::Acquire), 16);
+    assert_eq!(buf.sync_limit(Ordering::Acquire), 16);
+
+    buf.atomic_set_limit(8, Ordering::Release);
+    buf.try_resize(32).unwrap();
+    assert_eq!(buf.sync_limit(Ordering::Acquire), 8);
+}
+
+#[test]
+#[should_panic]
+fn test_atomic_set_limit_overflow() {
+    let mut buf = HBuf::allocate_zeroed(64);
+    buf.enable_atomic_limit();
+    buf.atomic_set_limit(65, Ordering::Release);
+}
+
+#[test]
+fn test_ord() {
+    let mut bufs: Vec<HBuf> = [&[1u8, 2, 3][..], &[2], &[1, 2], &[], &[1, 3]].iter().map(|data| {
+        let mut buf = HBuf::allocate_zeroed(8);
+        buf.set_limit(data.len());
+        buf.copy_from_slice(0, data);
+        buf
+    }).collect();
+
+    bufs.sort();
+    let sorted: Vec<&[u8]> = bufs.iter().map(|b| b.as_slice()).collect();
+    assert_eq!(sorted, vec![&[][..], &[1, 2], &[1, 2, 3], &[1, 3], &[2]]);
+    assert!(bufs[1] < bufs[2]);
+
+    let mut map = std::collections::BTreeMap::new();
+    for (i, buf) in bufs.into_iter().enumerate() {
+        map.insert(buf, i);
+    }
+    assert_eq!(map.get(&[1u8, 3][..]), Some(&3));
+}
+
+#[test]
+fn test_into_raw_parts() {
+    let mut vec = Vec::with_capacity(16);
+    vec.extend_from_slice(&[1, 2, 3]);
+    let (data, capacity, limit) = HBuf::from(vec).into_raw_parts();
+    assert_eq!((capacity, limit), (16, 3));
+    let vec = unsafe { Vec::from_raw_parts(data, limit, capacity) };
+    assert_eq!(vec, vec![1, 2, 3]);
+
+    let mut buf = HBuf::allocate_aligned_zeroed(64, 64);
+    buf.copy_from_slice(0, &[7, 8]);
+    let (data, capacity, limit) = buf.into_raw_parts();
+    let buf = unsafe {
+        HBuf::from_raw_parts_with_destructor(data, capacity, |data, size| {
+            std::alloc::dealloc(data, std::alloc::Layout::from_size_align(size, 64).unwrap())
+        })
+    };
+    assert_eq!(limit, 64);
+    assert_eq!(&buf[..3], &[7, 8, 0]);
+    assert!(buf.has_destructor());
+}
+
+#[test]
+fn test_leak() {
+    let buf = HBuf::from(vec![1u8, 2, 3]);
+    let other = buf.clone();
+    let buf = buf.leak().unwrap_err();
+    assert_eq!(buf.ref_count(), 2);
+    drop(other);
+
+    let leaked: &'static mut [u8] = buf.leak().unwrap();
+    assert_eq!(leaked, &[1, 2, 3]);
+    leaked[0] = 9;
+    assert_eq!(leaked, &[9, 2, 3]);
+}
+
+#[test]
+fn test_try_get() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.copy_from_slice(12, &0xDEADBEEFu32.to_ne_bytes());
+    buf.set_limit(15);
+    assert_eq!(buf.try_get_u32(8), Some(0));
+    assert_eq!(buf.try_get_u32(12), None);
+    assert_eq!(buf.try_get_u32(usize::MAX), None);
+    assert_eq!(buf.try_get_u8(14), Some(buf[14]));
+    assert_eq!(buf.try_get_u8(15), None);
+
+    buf.set_limit(16);
+    assert_eq!(buf.try_get_u32(12), Some(0xDEADBEEF));
+    unsafe {
+        assert_eq!(buf.try_get::<u32>(12), Some(0xDEADBEEF));
+        assert_eq!(buf.try_get::<u32>(13), None);
+        assert_eq!(buf.try_get::<u64>(usize::MAX - 2), None);
+    }
+}
+
+#[test]
+fn test_try_set() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(15);
+    assert!(buf.try_set_u32(8, 0xDEADBEEF).is_ok());
+    assert_eq!(buf.get_u32(8), 0xDEADBEEF);
+    assert!(matches!(buf.try_set_u32(12, 1), Err(HBufError::OutOfBounds { .. })));
+    assert!(matches!(buf.try_set_u32(usize::MAX, 1), Err(HBufError::OutOfBounds { .. })));
+    assert!(buf.try_set_u8(14, 5).is_ok());
+    assert!(matches!(buf.try_set_u8(15, 5), Err(HBufError::OutOfBounds { .. })));
+
+    unsafe {
+        assert!(buf.try_set::<u16>(13, 0xFFFF).is_ok());
+        assert!(matches!(buf.try_set::<u16>(14, 1), Err(HBufError::OutOfBounds { .. })));
+        assert!(matches!(buf.try_set::<u64>(usize::MAX - 2, 1), Err(HBufError::OutOfBounds { .. })));
+    }
+
+    buf.set_limit(16);
+    assert_eq!(&buf[12..16], &[0, 0xFF, 0xFF, 0]);
+
+    let err: std::io::Error = HBufError::OutOfBounds { index: 12, limit: 15 }.into();
+    assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_out_of_bounds_error() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(10);
+    let err = buf.try_set_u32(8, 1).unwrap_err();
+    assert!(matches!(err, HBufError::OutOfBounds { index: 8, limit: 10 }));
+
+    let display = err.to_string();
+    assert!(display.contains('8'));
+    assert!(display.contains("10"));
+
+    let io_err: std::io::Error = err.into();
+    assert_eq!(io_err.kind(), std::io::ErrorKind::UnexpectedEof);
+    assert_eq!(io_err.to_string(), "Index 8 is out of bounds for HBuf with limit 10");
+}
+
+#[test]
+fn test_set_known_type() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_u32(0, 0xDEADBEEF);
+    buf.set_i16(4, -2);
+    buf.set_f64(8, 1.5);
+    assert_eq!(buf.get_u32(0), 0xDEADBEEF);
+    assert_eq!(buf.get_i16(4), -2);
+    assert_eq!(buf.get_f64(8), 1.5);
+}
+
+#[test]
+fn test_unchecked() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    unsafe {
+        buf.set_u32_unchecked(12, 0xDEADBEEF);
+        assert_eq!(buf.get_u32_unchecked(12), 0xDEADBEEF);
+        assert_eq!(*buf.get_ref_unchecked::<u32>(12), 0xDEADBEEF);
+    }
+    assert_eq!(buf.get_u32(12), 0xDEADBEEF);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "out of bounds")]
+fn test_checked_get_panics() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(15);
+    buf.get_u32(12);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "out of bounds")]
+fn test_checked_set_panics() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_u64(9, 1);
+}
+
+#[test]
+fn test_shrink_to_fit() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(1024);
+    buf.fill_with(|i| i as u8);
+    buf.set_limit(100);
+    buf.shrink_to_fit()?;
+    assert_eq!(buf.capacity(), 100);
+    assert_eq!(buf.limit(), 100);
+    assert_eq!(buf[99], 99);
+    buf.shrink_to_fit()?;
+    assert_eq!(buf.capacity(), 100);
+
+    let shared = buf.clone();
+    buf.set_limit(50);
+    assert!(matches!(buf.shrink_to_fit(), Err(HBufError::Shared)));
+    drop(shared);
+
+    buf.set_limit(0);
+    assert!(matches!(buf.shrink_to_fit(), Err(HBufError::ZeroSize)));
+    assert_eq!(buf.capacity(), 100);
+
+    fn noop(_: *mut u8, _: usize) {}
+    let mut x = vec![0u8; 16];
+    let mut raw = unsafe { HBuf::from_raw_parts_with_destructor(x.as_mut_ptr(), x.len(), noop) };
+    raw.set_limit(8);
+    assert!(matches!(raw.shrink_to_fit(), Err(HBufError::NotOwned)));
+    assert_eq!(raw.capacity(), 16);
+
+    return Ok(());
+}
+
+#[test]
+fn test_empty() {
+    let mut buf = HBuf::empty();
+    assert!(buf.as_slice().is_empty());
+    assert!(buf.as_mut_slice().is_empty());
+    assert_eq!(buf.capacity(), 0);
+    assert!(!buf.has_destructor());
+    assert_eq!(buf.as_slice_u64().map(|s| s.len()), Some(0));
+    assert_eq!(buf.try_get_u8(0), None);
+    assert_eq!(buf.to_hex_string(), "");
+    buf.fill(1);
+    buf.copy_from_slice(0, &[]);
+    assert_eq!(buf, HBuf::default());
+
+    let copy = buf.deep_clone();
+    assert_eq!(copy.capacity(), 0);
+    let copy = HBuf::concat(&[&buf, &copy]);
+    assert_eq!(copy.capacity(), 0);
+    assert_eq!(HBuf::concat(&[]).capacity(), 0);
+    let _ = format!("{}", buf);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_empty_index() {
+    let buf = HBuf::default();
+    let _ = buf[0];
+}
+
+#[test]
+fn test_try_split_overflow() {
+    let buf = HBuf::allocate_zeroed(16);
+    assert!(buf.try_split(usize::MAX, 2).is_none());
+    assert!(buf.try_split(2, usize::MAX).is_none());
+    assert!(buf.try_split(8, 8).is_some());
+}
+
+#[test]
+#[should_panic(expected = "capacity of the source buffer is only 16")]
+fn test_split_overflow() {
+    let buf = HBuf::allocate_zeroed(16);
+    let _ = buf.split(usize::MAX, 2);
+}
+
+#[test]
+fn test_as_ptr_at() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(8);
+    assert_eq!(buf.as_ptr_at(0), buf.as_ptr() as *const u8);
+    assert_eq!(buf.as_ptr_at(5), buf.as_ptr().wrapping_add(5) as *const u8);
+    assert_eq!(buf.as_mut_ptr_at(8), buf.as_ptr().wrapping_add(8));
+    unsafe { buf.as_mut_ptr_at(3).write(7) };
+    assert_eq!(buf[3], 7);
+}
+
+#[test]
+#[should_panic(expected = "Index 9 is out of bounds")]
+fn test_as_ptr_at_out_of_bounds() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(8);
+    buf.as_ptr_at(9);
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TestHeader {
+    magic: u32,
+    version: u16,
+    flags: u16,
+    length: u64,
+}
+
+#[test]
+fn test_as_ref_struct() {
+    let mut buf = HBuf::allocate_aligned_zeroed(32, 8);
+    buf.set_u32(0, 0xCAFEBABE);
+    buf.set_u16(4, 3);
+    buf.set_u64(8, 1024);
+
+    unsafe {
+        let header = buf.as_ref_struct::<TestHeader>().unwrap();
+        assert_eq!(*header, TestHeader { magic: 0xCAFEBABE, version: 3, flags: 0, length: 1024 });
+
+        buf.as_mut_struct::<TestHeader>().unwrap().flags = 0x8001;
+        assert_eq!(buf.get_u16(6), 0x8001);
+
+        assert!(buf.split(4, 16).as_ref_struct::<TestHeader>().is_none());
+        buf.set_limit(15);
+        assert!(buf.as_ref_struct::<TestHeader>().is_none());
+        assert!(buf.as_mut_struct::<TestHeader>().is_none());
+    }
+}
+
+#[test]
+fn test_endian_get_set() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.copy_from_slice(2, &[0x12, 0x34, 0x56, 0x78]);
+    buf.set_position(1);
+    assert_eq!(buf.get_u32_le(2), 0x78563412);
+    assert_eq!(buf.get_u32_be(2), 0x12345678);
+    assert_eq!(buf.get_u16_be(2), 0x1234);
+    assert_eq!(buf.get_i16_le(4), 0x7856);
+
+    buf.set_u32_be(8, 0xAABBCCDD);
+    assert_eq!(&buf[8..12], &[0xAA, 0xBB, 0xCC, 0xDD]);
+    buf.set_u32_le(8, 0xAABBCCDD);
+    assert_eq!(&buf[8..12], &[0xDD, 0xCC, 0xBB, 0xAA]);
+    buf.set_f64_be(8, 1.5);
+    assert_eq!(buf.get_f64_be(8), 1.5);
+    assert_eq!(buf.position(), 1);
+}
+
+#[test]
+#[should_panic(expected = "out of bounds")]
+fn test_endian_get_out_of_bounds() {
+    let buf = HBuf::allocate_zeroed(16);
+    buf.get_u64_be(12);
+}
+
+#[test]
+fn test_publish_acquire() {
+    let buf = HBuf::allocate_aligned_zeroed(4096, 8);
+    let producer = buf.clone();
+    let t = thread::spawn(move || {
+        let mut producer = producer;
+        for round in 1..=100u32 {
+            while producer.acquire_u32(0) != round - 1 {
+                std::hint::spin_loop();
+            }
+            producer[64..4096].fill(round as u8);
+            producer.publish_u32(4, round);
+        }
+    });
+
+    for round in 1..=100u32 {
+        while buf.acquire_u32(4) != round {
+            std::hint::spin_loop();
+        }
+        assert!(buf[64..4096].iter().all(|b| *b == round as u8));
+        buf.publish_u32(0, round);
+    }
+
+    t.join().unwrap();
+    HBuf::fence(Ordering::SeqCst);
+}
+
+#[test]
+fn test_bool() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.set_bool(1, true);
+    buf.set_bool(3, true);
+    buf.set_bool(3, false);
+    buf.set_bool(7, true);
+    assert!(buf.get_bool(1));
+    assert!(!buf.get_bool(3));
+    assert_eq!(buf.as_slice_bool(), &[false, true, false, false, false, false, false, true]);
+
+    buf.as_mut_slice_bool()[2] = true;
+    assert_eq!(buf[2], 1);
+
+    buf[4] = 7;
+    assert!(buf.get_bool(4));
+}
+
+#[test]
+#[should_panic(expected = "Byte 2 at index 5 is not a valid bool")]
+fn test_bool_invalid() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf[5] = 2;
+    buf.as_slice_bool();
+}
+
+#[test]
+fn test_as_slice_exact() {
+    let mut buf = HBuf::allocate_aligned_zeroed(16, 8);
+    buf.set_limit(10);
+    unsafe {
+        assert!(buf.as_slice_exact::<u32>().is_none());
+        assert!(buf.as_mut_slice_exact::<u32>().is_none());
+        assert_eq!(buf.as_slice_generic::<u32>().map(|s| s.len()), Some(2));
+        assert_eq!(buf.as_slice_exact::<u16>().map(|s| s.len()), Some(5));
+
+        buf.set_limit(12);
+        buf.as_mut_slice_exact::<u32>().unwrap()[2] = 0xFFFFFFFF;
+        assert_eq!(buf.as_slice_exact::<u32>(), Some(&[0, 0, 0xFFFFFFFF][..]));
+        assert!(buf.split(2, 12).as_slice_exact::<u32>().is_none());
+    }
+}
+
+#[test]
+fn test_allocate_cache_aligned() {
+    let line = HBuf::cache_line_size();
+    assert!(line.is_power_of_two());
+    let buf = HBuf::allocate_cache_aligned_zeroed(4 * line);
+    assert_eq!(buf.as_ptr() as usize % line, 0);
+    assert!(buf.iter().all(|b| *b == 0));
+
+    let buf = HBuf::allocate_cache_aligned(1);
+    assert!(buf.is_aligned_to(line));
+
+    let counters = HBuf::allocate_cache_aligned_zeroed(4 * line);
+    let threads: Vec<_> = (0..4).map(|i| {
+        let counters = counters.clone();
+        thread::spawn(move || {
+            for _ in 0..1000 {
+                counters.atomic_fetch_add_u64(i * line, 1, Ordering::Relaxed);
+            }
+        })
+    }).collect();
+    for t in threads {
+        t.join().unwrap();
+    }
+    assert!((0..4).all(|i| counters.atomic_load_u64(i * line, Ordering::Relaxed) == 1000));
+}
+
+#[test]
+fn test_split_chunks() {
+    let buf = HBuf::allocate_zeroed(100);
+    let chunks: Vec<HBuf> = buf.split_chunks(32).collect();
+    assert_eq!(chunks.iter().map(|c| c.capacity()).collect::<Vec<_>>(), vec![32, 32, 32, 4]);
+    assert_eq!(chunks[1].as_ptr(), buf.as_ptr().wrapping_add(32));
+    assert_eq!(chunks[3].limit(), 4);
+
+    let workers: Vec<_> = chunks.into_iter().enumerate().map(|(i, mut chunk)| {
+        thread::spawn(move || chunk.fill(i as u8))
+    }).collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert_eq!(buf[31], 0);
+    assert_eq!(buf[32], 1);
+    assert_eq!(buf[99], 3);
+
+    assert_eq!(buf.split_chunks(100).count(), 1);
+    assert_eq!(HBuf::empty().split_chunks(8).count(), 0);
+}
+
+#[test]
+fn test_starts_ends_contains() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.copy_from_slice(0, b"\x7fELF\x02\x01\x01");
+    buf.set_limit(7);
+    buf.set_position(4);
+
+    assert!(buf.starts_with(b"\x7fELF"));
+    assert!(!buf.starts_with(b"MZ"));
+    assert!(buf.ends_with(&[1, 1]));
+    assert!(!buf.ends_with(&[0]));
+    assert!(buf.contains(b"ELF"));
+    assert!(!buf.contains(b"ELF\x03"));
+    assert!(buf.contains(b""));
+    assert_eq!(buf.find_subslice(b"ELF"), None);
+
+    buf.set_limit(3);
+    assert!(!buf.starts_with(b"\x7fELF"));
+    assert!(!buf.contains(b"ELF"));
+}
+
+#[test]
+fn test_duplicate() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.set_u8(10, 7);
+    buf.set_limit(20);
+    buf.set_position(10);
+
+    let dup = buf.duplicate();
+    assert_eq!(dup.position(), 0);
+    assert_eq!(dup.limit(), 32);
+    assert_eq!(dup.as_ptr(), buf.as_ptr());
+    assert_eq!(buf.position(), 10);
+    assert_eq!(buf.limit(), 20);
+    assert_eq!(buf.ref_count(), 2);
+
+    let reader = thread::spawn(move || dup[10]);
+    assert_eq!(reader.join().unwrap(), 7);
+    assert_eq!(buf.ref_count(), 1);
+}
+
+#[test]
+fn test_replace_byte() {
+    let mut buf = HBuf::allocate_zeroed(100);
+    buf.fill_with(|index| if index % 3 == 0 { 0 } else { 1 });
+    buf.set_u8(99, 0);
+    buf.set_limit(99);
+
+    assert_eq!(buf.count_byte(0), 33);
+    assert_eq!(buf.replace_byte(0, 0xFF), 33);
+    assert_eq!(buf.count_byte(0), 0);
+    assert_eq!(buf.count_byte(0xFF), 33);
+    assert_eq!(buf.get_u8(0), 0xFF);
+    assert_eq!(buf.get_u8(1), 1);
+    assert_eq!(buf.replace_byte(0, 0xFF), 0);
+
+    buf.set_limit(100);
+    assert_eq!(buf.get_u8(99), 0);
+}
+
+#[test]
+fn test_replace_bytes() {
+    let mut buf = HBuf::from(b"a\r\nb\r\n\r\r\n".to_vec());
+    assert_eq!(buf.replace_bytes(b"\r\n", b"\n\n"), 3);
+    assert_eq!(buf.as_slice(), b"a\n\nb\n\n\r\n\n");
+    assert_eq!(buf.replace_bytes(b"", b""), 0);
+
+    let mut buf = HBuf::from(b"aaaa".to_vec());
+    assert_eq!(buf.replace_bytes(b"aa", b"ba"), 2);
+    assert_eq!(buf.as_slice(), b"baba");
+}
+
+#[test]
+#[should_panic]
+fn test_replace_bytes_length_mismatch() {
+    HBuf::allocate_zeroed(8).replace_bytes(b"ab", b"a");
+}
+
+#[test]
+fn test_set_limit_clamped() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_position(12);
+    assert_eq!(buf.set_limit_clamped(10), 10);
+    assert_eq!(buf.limit(), 10);
+    assert_eq!(buf.position(), 10);
+    assert_eq!(buf.set_limit_clamped(16), 16);
+    assert_eq!(buf.limit(), 16);
+    assert_eq!(buf.set_limit_clamped(17), 16);
+    assert_eq!(buf.set_limit_clamped(usize::MAX), 16);
+    assert_eq!(buf.limit(), 16);
+}
+
+#[test]
+fn test_as_mut_slice_compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/as_mut_slice_alias.rs");
+}
+
+#[test]
+fn test_save_restore_state() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.set_limit(20);
+    buf.set_position(4);
+    let state = buf.save_state();
+    assert_eq!(state, BufState { position: 4, limit: 20 });
+
+    buf.set_limit(32);
+    buf.set_position(30);
+    buf.mark();
+    buf.restore_state(state);
+    assert_eq!(buf.position(), 4);
+    assert_eq!(buf.limit(), 20);
+    assert!(buf.reset_to_mark().is_err());
+
+    buf.set_limit(8);
+    buf.restore_state(state);
+    assert_eq!(buf.position(), 4);
+    assert_eq!(buf.limit(), 20);
+}
+
+#[test]
+#[should_panic]
+fn test_restore_state_after_resize() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    let state = buf.save_state();
+    buf.try_resize(16).unwrap();
+    buf.restore_state(state);
+}
+
+#[test]
+fn test_split_aligned() {
+    let buf = HBuf::allocate_aligned_zeroed(64, 8);
+    assert!(buf.split_aligned(4, 16, 8).is_none());
+    assert!(buf.split_aligned(4, 16, 3).is_none());
+    assert!(buf.split_aligned(8, 64, 8).is_none());
+    assert!(buf.split_aligned(72, 0, 8).is_none());
+
+    let mut sub = buf.split_aligned(8, 16, 8).unwrap();
+    assert_eq!(sub.as_ptr(), buf.as_ptr().wrapping_add(8));
+    assert_eq!(sub.capacity(), 16);
+    assert_eq!(sub.as_mut_slice_u64().unwrap().len(), 2);
+    assert_eq!(buf.split_aligned(4, 16, 4).unwrap().as_slice_u32().unwrap().len(), 4);
+}
+
+#[test]
+fn test_atomic_copy_to() {
+    let buf = HBuf::allocate_aligned_zeroed(67, 8).split(3, 64);
+    for index in 0..buf.limit() {
+        buf.store_u8(index, 0xAA, Ordering::Relaxed);
+    }
+
+    let writer_buf = buf.clone();
+    let writer = thread::spawn(move || {
+        for round in 0..2000 {
+            let value = if round % 2 == 0 { 0x55 } else { 0xAA };
+            for index in 0..writer_buf.limit() {
+                writer_buf.store_u8(index, value, Ordering::Relaxed);
+            }
+        }
+    });
+
+    let mut snapshot = [0u8; 64];
+    while !writer.is_finished() {
+        assert_eq!(buf.atomic_copy_to(&mut snapshot, Ordering::Relaxed), 64);
+        assert!(snapshot.iter().all(|b| *b == 0x55 || *b == 0xAA));
+    }
+    writer.join().unwrap();
+
+    let mut small = [0u8; 10];
+    assert_eq!(buf.atomic_copy_to(&mut small, Ordering::Acquire), 10);
+    assert_eq!(small, [0xAA; 10]);
+}
+
+#[test]
+fn test_from_slice() {
+    let mut source = vec![1u8, 2, 3, 4, 5];
+    let buf = HBuf::from_slice(&source);
+    source[0] = 9;
+    assert_eq!(buf.as_slice(), &[1, 2, 3, 4, 5]);
+    assert_eq!(buf.capacity(), 5);
+    assert_ne!(buf.as_ptr().cast_const(), source.as_ptr());
+
+    let buf = HBuf::from_slice_aligned(&source, 64);
+    source[1] = 9;
+    assert_eq!(buf.as_slice(), &[9, 2, 3, 4, 5]);
+    assert_eq!(buf.as_ptr().align_offset(64), 0);
+
+    assert_eq!(HBuf::from_slice(&[]).capacity(), 0);
+    let empty = HBuf::from_slice_aligned(&[], 4096);
+    assert_eq!(empty.capacity(), 0);
+    assert!(empty.is_aligned_to(4096));
+    assert!(empty.as_slice().is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_from_slice_aligned_invalid_alignment() {
+    HBuf::from_slice_aligned(&[], 3);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "bytes_support")]
+
+use bytes::{Buf, BufMut};
+
+use heapbuf::HBuf;
+
+#[test]
+fn test_buf_round_trip() {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.put_u32(0x11223344);
+    buf.put_u16_le(0x5566);
+    buf.put_u8(0x77);
+    buf.put_slice(&[1, 2, 3]);
+    assert_eq!(buf.position(), 10);
+    assert_eq!(buf.remaining_mut(), 22);
+    buf.flip();
+
+    assert_eq!(&buf[0..4], &[0x11, 0x22, 0x33, 0x44]);
+    assert_eq!(Buf::remaining(&buf), 10);
+    assert_eq!(Buf::get_u32(&mut buf), 0x11223344);
+    assert_eq!(Buf::get_u16_le(&mut buf), 0x5566);
+    assert_eq!(Buf::get_u8(&mut buf), 0x77);
+    assert_eq!(buf.chunk(), &[1, 2, 3]);
+    buf.advance(3);
+    assert_eq!(buf.position(), 10);
+    assert!(!buf.has_remaining());
+}
+
+#[test]
+#[should_panic]
+fn test_advance_past_remaining() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.set_position(4);
+    buf.advance(5);
+}
+
+#[test]
+#[should_panic]
+fn test_put_past_remaining() {
+    let mut buf = HBuf::allocate_zeroed(3);
+    buf.put_u32(1);
+}
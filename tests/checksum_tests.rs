@@ -0,0 +1,22 @@
+#![cfg(feature = "checksum_support")]
+
+use heapbuf::HBuf;
+
+#[test]
+fn test_checksums() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.copy_from_slice(2, b"123456789");
+    buf.set_position(5);
+
+    assert_eq!(buf.crc32_range(2..11), 0xCBF43926);
+    assert_eq!(buf.adler32_range(2..11), 0x091E01DE);
+    assert_eq!(buf.position(), 5);
+
+    let sub = buf.split(2, 9);
+    assert_eq!(sub.crc32(), 0xCBF43926);
+    assert_eq!(sub.adler32(), 0x091E01DE);
+
+    buf.set_limit(0);
+    assert_eq!(buf.crc32(), 0);
+    assert_eq!(buf.adler32(), 1);
+}
@@ -1,8 +1,34 @@
 use heapbuf::DynDestructor;
+use std::alloc::{GlobalAlloc, Layout, System};
 use std::ptr::null_mut;
-use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering};
 use std::sync::Arc;
 
+static TRACKED: AtomicUsize = AtomicUsize::new(0);
+static TRACKED_ZEROED: AtomicBool = AtomicBool::new(false);
+
+///
+/// Checks whether the memory at TRACKED is zeroed right before it is deallocated.
+///
+struct CheckingAllocator;
+
+unsafe impl GlobalAlloc for CheckingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if TRACKED.compare_exchange(ptr as usize, 0, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            let zeroed = std::slice::from_raw_parts(ptr, layout.size()).iter().all(|b| *b == 0);
+            TRACKED_ZEROED.store(zeroed, Ordering::SeqCst);
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CheckingAllocator = CheckingAllocator;
+
 static PTR: AtomicPtr<u8> = AtomicPtr::new(null_mut());
 
 static SZ: AtomicUsize = AtomicUsize::new(0);
@@ -86,3 +112,53 @@ fn test_dyn_destructor_called() {
     assert_eq!(des1.0.load(Ordering::SeqCst), 1);
     assert_eq!(des2.0.load(Ordering::SeqCst), 2);
 }
+
+#[test]
+fn test_has_destructor() {
+    let hb = heapbuf::HBuf::allocate(16);
+    assert!(hb.has_destructor());
+    assert!(hb.clone().has_destructor());
+    assert!(hb.split(4, 4).has_destructor());
+
+    let mut x = vec![0u8; 16];
+    let ptr = x.as_mut_ptr();
+    let hb = unsafe { heapbuf::HBuf::from_raw_parts(ptr, 16) };
+    assert!(!hb.has_destructor());
+    drop(hb);
+
+    fn noop(_: *mut u8, _: usize) {}
+    let hb = unsafe { heapbuf::HBuf::from_raw_parts_with_destructor(ptr, 16, noop) };
+    assert!(hb.has_destructor());
+}
+
+#[test]
+fn test_zeroize() {
+    let mut x = vec![0xAAu8; 16];
+    let mut hb = unsafe { heapbuf::HBuf::from_raw_parts(x.as_mut_ptr(), 8) };
+    hb.set_limit(4);
+    hb.zeroize();
+    drop(hb);
+    assert_eq!(&x[..8], &[0u8; 8]);
+    assert_eq!(&x[8..], &[0xAAu8; 8]);
+}
+
+#[test]
+fn test_allocate_secure() {
+    let mut hb = heapbuf::HBuf::allocate_secure(32);
+    assert!(hb.has_destructor());
+    assert_eq!(hb.as_slice(), &[0u8; 32]);
+    hb.fill(0x55);
+
+    let clone = hb.clone();
+    drop(hb);
+    assert_eq!(clone.ref_count(), 1);
+    assert_eq!(clone.as_slice(), &[0x55u8; 32]);
+
+    let mut clone = clone;
+    assert!(clone.try_resize(64).is_err());
+
+    TRACKED.store(clone.as_ptr() as usize, Ordering::SeqCst);
+    drop(clone);
+    assert_eq!(TRACKED.load(Ordering::SeqCst), 0);
+    assert!(TRACKED_ZEROED.load(Ordering::SeqCst));
+}
@@ -0,0 +1,39 @@
+use std::sync::atomic::Ordering;
+use std::thread;
+use heapbuf::{FrozenHBuf, HBuf};
+
+#[test]
+fn test_freeze_thaw() {
+    let mut buf = HBuf::allocate_aligned_zeroed(16, 8);
+    buf.set_u32(0, 0xDEADBEEF);
+    buf.set_limit(8);
+
+    let frozen = buf.freeze();
+    assert_eq!(frozen.len(), 8);
+    assert_eq!(frozen.limit(), 8);
+    assert_eq!(frozen.capacity(), 16);
+    assert_eq!(frozen.get_u32(0), 0xDEADBEEF);
+    assert_eq!(frozen.try_get_u32(6), None);
+    assert_eq!(frozen.atomic_load_u32(0, Ordering::Acquire), 0xDEADBEEF);
+    assert_eq!(&frozen[4..], &[0, 0, 0, 0]);
+
+    let readers: Vec<_> = (0..4).map(|_| {
+        let frozen = frozen.clone();
+        thread::spawn(move || frozen.get_u32(0))
+    }).collect();
+    for reader in readers {
+        assert_eq!(reader.join().unwrap(), 0xDEADBEEF);
+    }
+
+    let mut buf = frozen.thaw();
+    buf[4] = 1;
+    let frozen = FrozenHBuf::from(buf);
+    assert_eq!(frozen.as_slice()[4], 1);
+    assert_eq!(frozen.get_u32(0), 0xDEADBEEF);
+}
+
+#[test]
+fn test_frozen_compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/frozen_mutation.rs");
+}
@@ -0,0 +1,26 @@
+#![cfg(all(feature = "hugepage_support", target_os = "linux"))]
+
+use heapbuf::HBuf;
+
+#[test]
+fn test_allocate_hugepage() {
+    let mut buf = match HBuf::allocate_hugepage(3 * 1024 * 1024) {
+        Ok(buf) => buf,
+        Err(_) => {
+            //Most hosts do not reserve any huge pages by default.
+            return;
+        }
+    };
+
+    assert_eq!(buf.capacity(), 3 * 1024 * 1024);
+    assert!(buf.is_aligned_to(2 * 1024 * 1024));
+    assert!(buf.as_slice_u64().is_some());
+    assert_eq!(buf.count_ones(), 0);
+    buf.fill(0x5A);
+    assert_eq!(buf[buf.limit() - 1], 0x5A);
+}
+
+#[test]
+fn test_allocate_hugepage_zero() {
+    assert!(HBuf::allocate_hugepage(0).is_err());
+}
@@ -0,0 +1,31 @@
+#![cfg(all(feature = "madvise_support", unix))]
+
+use heapbuf::{Advice, HBuf};
+
+#[test]
+fn test_advise() {
+    let mut buf = HBuf::allocate_zeroed(64 * 1024);
+    buf.fill(1);
+    buf.advise(Advice::Sequential).unwrap();
+    buf.advise(Advice::Random).unwrap();
+    buf.advise(Advice::WillNeed).unwrap();
+    buf.advise(Advice::Normal).unwrap();
+    assert_eq!(buf.count_byte(1), 64 * 1024);
+}
+
+#[test]
+fn test_advise_dont_need_keeps_neighbours() {
+    let buf = HBuf::allocate_aligned(3 * 4096, 4096);
+    let mut neighbour = buf.split(0, 100);
+    neighbour.fill(7);
+    let sub = buf.split(100, 200);
+    sub.advise(Advice::DontNeed).unwrap();
+    assert_eq!(neighbour.count_byte(7), 100);
+}
+
+#[test]
+fn test_advise_empty() {
+    let buf = HBuf::empty();
+    buf.advise(Advice::WillNeed).unwrap();
+    buf.advise(Advice::DontNeed).unwrap();
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "mlock_support")]
+
+use std::io::ErrorKind;
+use heapbuf::HBuf;
+
+#[test]
+fn test_mlock() {
+    let buf = HBuf::allocate_secure(4096);
+    match buf.mlock() {
+        Ok(()) => {
+            buf.munlock().unwrap();
+        }
+        Err(err) => {
+            //Locking memory requires privileges or a sufficient RLIMIT_MEMLOCK which CI machines may lack.
+            assert!(matches!(err.kind(), ErrorKind::PermissionDenied | ErrorKind::OutOfMemory | ErrorKind::WouldBlock), "{}", err);
+        }
+    }
+}
+
+#[test]
+fn test_mlock_split() {
+    let buf = HBuf::allocate(64);
+    let sub = buf.split(3, 10);
+    match sub.mlock() {
+        Ok(()) => {
+            sub.munlock().unwrap();
+        }
+        Err(err) => {
+            assert!(matches!(err.kind(), ErrorKind::PermissionDenied | ErrorKind::OutOfMemory | ErrorKind::WouldBlock), "{}", err);
+        }
+    }
+}
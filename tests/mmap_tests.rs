@@ -0,0 +1,56 @@
+#![cfg(feature = "mmap_support")]
+
+use std::path::PathBuf;
+
+use heapbuf::HBuf;
+
+fn temp_file(name: &str, data: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("heapbuf_{}_{}", std::process::id(), name));
+    std::fs::write(&path, data).unwrap();
+    path
+}
+
+#[test]
+fn test_mmap_read() -> std::io::Result<()> {
+    let path = temp_file("read", &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    let mut buf = HBuf::mmap_file(&path, false)?;
+    assert_eq!(buf.capacity(), 9);
+    assert_eq!(buf.limit(), 9);
+    assert!(buf.has_destructor());
+    assert_eq!(buf.get_u32(0), u32::from_ne_bytes([1, 2, 3, 4]));
+    assert_eq!(buf.as_slice_u32().unwrap().len(), 2);
+
+    //Copy on write, this never reaches the file.
+    buf[0] = 0xFF;
+    assert_eq!(buf[0], 0xFF);
+    drop(buf);
+    assert_eq!(std::fs::read(&path)?, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    std::fs::remove_file(&path)?;
+    return Ok(());
+}
+
+#[test]
+fn test_mmap_write() -> std::io::Result<()> {
+    let path = temp_file("write", &[0u8; 16]);
+    let mut buf = HBuf::mmap_file(&path, true)?;
+    buf.copy_from_slice(4, &0x11223344u32.to_ne_bytes());
+    let clone = buf.clone();
+    drop(buf);
+    assert_eq!(clone.get_u32(4), 0x11223344);
+    drop(clone);
+
+    let data = std::fs::read(&path)?;
+    assert_eq!(&data[4..8], &0x11223344u32.to_ne_bytes());
+
+    std::fs::remove_file(&path)?;
+    return Ok(());
+}
+
+#[test]
+fn test_mmap_empty() -> std::io::Result<()> {
+    let path = temp_file("empty", &[]);
+    assert!(HBuf::mmap_file(&path, false).is_err());
+    std::fs::remove_file(&path)?;
+    return Ok(());
+}
@@ -0,0 +1,66 @@
+#![cfg(all(feature = "mprotect_support", unix))]
+
+use std::alloc::Layout;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use heapbuf::{HBuf, Protection};
+
+#[test]
+fn test_protect() {
+    let page_size = HBuf::page_size();
+    let mut buf = HBuf::allocate_page(2 * page_size);
+    buf.fill(3);
+
+    buf.protect(Protection::ReadOnly).unwrap();
+    assert!(buf.iter().all(|b| *b == 3));
+    buf.protect(Protection::NoAccess).unwrap();
+    buf.protect(Protection::ReadWrite).unwrap();
+    buf[0] = 4;
+    assert_eq!(buf[0], 4);
+}
+
+#[test]
+fn test_protect_unaligned() {
+    let page_size = HBuf::page_size();
+    let buf = HBuf::allocate_page(page_size + 1);
+    assert_eq!(buf.protect(Protection::ReadOnly).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+    let buf = HBuf::allocate_page(2 * page_size).split(1, page_size);
+    assert_eq!(buf.protect(Protection::ReadOnly).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+static DESTRUCTOR_PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+fn write_and_free(ptr: *mut u8, size: usize) {
+    //Faults if the protection was not reset before the destructor runs.
+    unsafe {
+        ptr.write_bytes(0, size);
+        std::alloc::dealloc(ptr, Layout::from_size_align(size, DESTRUCTOR_PAGE_SIZE.load(Ordering::SeqCst)).unwrap());
+    }
+}
+
+#[test]
+fn test_protect_reset_on_drop() {
+    let page_size = HBuf::page_size();
+    DESTRUCTOR_PAGE_SIZE.store(page_size, Ordering::SeqCst);
+    let layout = Layout::from_size_align(2 * page_size, page_size).unwrap();
+    let buf = unsafe { HBuf::from_raw_parts_with_destructor(std::alloc::alloc(layout), 2 * page_size, write_and_free) };
+
+    let clone = buf.clone();
+    buf.split(page_size, page_size).protect(Protection::NoAccess).unwrap();
+    buf.protect(Protection::ReadOnly).unwrap();
+    drop(buf);
+    drop(clone);
+
+    let mut buf = HBuf::allocate_page(2 * page_size);
+    buf.protect(Protection::ReadOnly).unwrap();
+    buf.protect(Protection::ReadWrite).unwrap();
+    buf.fill(1);
+}
+
+#[test]
+fn test_protect_without_destructor() {
+    let page_size = HBuf::page_size();
+    let owner = HBuf::allocate_page(page_size);
+    let buf = unsafe { HBuf::from_raw_parts(owner.as_ptr(), page_size) };
+    assert_eq!(buf.protect(Protection::ReadOnly).unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
@@ -0,0 +1,23 @@
+#![cfg(feature = "page_support")]
+
+use heapbuf::HBuf;
+
+#[test]
+fn test_page_size() {
+    let page_size = HBuf::page_size();
+    assert!(page_size.is_power_of_two());
+    assert!(page_size >= 4096);
+    assert_eq!(HBuf::page_size(), page_size);
+}
+
+#[test]
+fn test_allocate_page() {
+    let page_size = HBuf::page_size();
+    let buf = HBuf::allocate_page(100);
+    assert_eq!(buf.capacity(), 100);
+    assert_eq!(buf.as_ptr() as usize % page_size, 0);
+
+    let buf = HBuf::try_allocate_page(3 * page_size).unwrap();
+    assert!(buf.is_aligned_to(page_size));
+    assert!(HBuf::try_allocate_page(0).is_err());
+}
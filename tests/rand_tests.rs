@@ -0,0 +1,21 @@
+#![cfg(feature = "rand_support")]
+
+use heapbuf::HBuf;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[test]
+fn test_fill_random() {
+    let mut rng = StdRng::seed_from_u64(1234);
+    let mut buf = HBuf::allocate_zeroed(256);
+    buf.set_limit(128);
+    buf.fill_random(&mut rng);
+    assert!(buf.count_byte(0) < 16);
+
+    buf.set_limit(256);
+    assert!(buf[128..].iter().all(|b| *b == 0));
+
+    let mut other = HBuf::allocate_zeroed(128);
+    other.fill_random(&mut StdRng::seed_from_u64(1234));
+    assert_eq!(other.as_slice(), &buf[..128]);
+}
@@ -1,9 +1,7 @@
-use std::io::{ErrorKind, Seek, SeekFrom};
+use std::io::{BufRead, ErrorKind, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
 
-use rw_utils::num_read::NumRead;
-use rw_utils::num_write::NumWrite;
 
-use heapbuf::HBuf;
+use heapbuf::{ByteOrder, GrowingWriter, HBuf};
 
 #[test]
 fn test_read_write() -> std::io::Result<()> {
@@ -99,4 +97,488 @@ fn test_limit() -> std::io::Result<()> {
     assert_eq!(buf.limit(), 113);
 
     return Ok(());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_partial_read_write() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(8);
+    assert_eq!(buf.write(&[1, 2, 3, 4, 5, 6])?, 6);
+    assert_eq!(buf.position(), 6);
+    assert_eq!(buf.write(&[7, 8, 9, 10])?, 2);
+    assert_eq!(buf.position(), 8);
+    assert_eq!(buf.write(&[11])?, 0);
+    assert_eq!(buf.as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+    buf.flip();
+    let mut dst = [0u8; 5];
+    assert_eq!(buf.read(&mut dst)?, 5);
+    assert_eq!(dst, [1, 2, 3, 4, 5]);
+    assert_eq!(buf.position(), 5);
+    assert_eq!(buf.read(&mut dst)?, 3);
+    assert_eq!(&dst[..3], &[6, 7, 8]);
+    assert_eq!(buf.position(), 8);
+    assert_eq!(buf.read(&mut dst)?, 0);
+
+    buf.set_position(4);
+    let rest: Vec<u8> = buf.bytes().collect::<std::io::Result<Vec<u8>>>()?;
+    assert_eq!(rest, vec![5, 6, 7, 8]);
+
+    return Ok(());
+}
+
+#[test]
+fn test_io_copy() -> std::io::Result<()> {
+    let mut src = HBuf::allocate_zeroed(64);
+    for x in 0..40u8 {
+        src.write_u8(x)?;
+    }
+    src.flip();
+    src.set_position(8);
+
+    let mut dst = HBuf::allocate_zeroed(64);
+    dst.set_position(4);
+
+    let copied = std::io::copy(&mut src, &mut dst)?;
+    assert_eq!(copied, 32);
+    assert_eq!(src.position(), 40);
+    assert_eq!(src.remaining(), 0);
+    assert_eq!(dst.position(), 36);
+    assert_eq!(&dst.as_slice()[0..4], &[0, 0, 0, 0]);
+    for x in 0..32usize {
+        assert_eq!(dst[4 + x], (x + 8) as u8);
+    }
+
+    return Ok(());
+}
+
+#[test]
+fn test_read_to_end() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(128);
+    for x in 0..100u8 {
+        buf.write_u8(x)?;
+    }
+    buf.flip();
+    buf.seek(SeekFrom::Start(10))?;
+
+    let mut data = Vec::new();
+    assert_eq!(buf.read_to_end(&mut data)?, 90);
+    assert_eq!(data.len(), 90);
+    assert_eq!(data, (10..100u8).collect::<Vec<u8>>());
+    assert_eq!(buf.position(), 100);
+    assert_eq!(buf.read_to_end(&mut data)?, 0);
+    assert_eq!(data.len(), 90);
+
+    return Ok(());
+}
+
+#[test]
+fn test_read_write_at() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(64);
+    assert_eq!(buf.write_at(50, &[1, 2, 3, 4])?, 4);
+    assert_eq!(buf.position(), 0);
+
+    let mut dst = [0u8; 4];
+    assert_eq!(buf.read_at(50, &mut dst)?, 4);
+    assert_eq!(dst, [1, 2, 3, 4]);
+    assert_eq!(buf.position(), 0);
+
+    assert_eq!(buf.write_at(60, &[9, 9, 9, 9, 9, 9])?, 4);
+    let mut dst = [0u8; 8];
+    assert_eq!(buf.read_at(60, &mut dst)?, 4);
+    assert_eq!(&dst[..4], &[9, 9, 9, 9]);
+    assert_eq!(buf.read_at(64, &mut dst)?, 0);
+    assert_eq!(buf.position(), 0);
+
+    match buf.read_at(65, &mut dst).unwrap_err().kind() {
+        ErrorKind::UnexpectedEof => {}
+        _ => panic!("Unexpected error")
+    }
+    match buf.write_at(65, &dst).unwrap_err().kind() {
+        ErrorKind::UnexpectedEof => {}
+        _ => panic!("Unexpected error")
+    }
+
+    buf.set_position(3);
+    assert_eq!(buf.write_at(0, &[7])?, 1);
+    assert_eq!(buf.position(), 3);
+    assert_eq!(buf[0], 7);
+
+    return Ok(());
+}
+
+#[test]
+fn test_compact() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.write_all(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10])?;
+    buf.flip();
+    let mut dst = [0u8; 4];
+    buf.read_exact(&mut dst)?;
+    assert_eq!(dst, [1, 2, 3, 4]);
+
+    buf.compact();
+    assert_eq!(buf.position(), 6);
+    assert_eq!(buf.limit(), 32);
+    assert_eq!(&buf[..6], &[5, 6, 7, 8, 9, 10]);
+
+    buf.write_all(&[11, 12])?;
+    buf.flip();
+    assert_eq!(buf.as_slice(), &[5, 6, 7, 8, 9, 10, 11, 12]);
+
+    return Ok(());
+}
+
+#[test]
+fn test_mark() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.write_all(&[1, 2, 3, 4, 5, 6, 7, 8])?;
+    buf.flip();
+    assert!(buf.reset_to_mark().is_err());
+
+    assert_eq!(buf.read_u8()?, 1);
+    buf.mark();
+    assert_eq!(buf.read_u8()?, 2);
+    assert_eq!(buf.read_u8()?, 3);
+    assert_eq!(buf.position(), 3);
+
+    buf.reset_to_mark()?;
+    assert_eq!(buf.position(), 1);
+    assert_eq!(buf.read_u8()?, 2);
+    buf.reset_to_mark()?;
+    assert_eq!(buf.read_u8()?, 2);
+
+    buf.set_limit(4);
+    buf.reset_to_mark()?;
+    assert_eq!(buf.position(), 1);
+
+    buf.seek(SeekFrom::Start(3))?;
+    buf.mark();
+    buf.set_limit(2);
+    match buf.reset_to_mark().unwrap_err().kind() {
+        ErrorKind::InvalidInput => {}
+        _ => panic!("Unexpected error")
+    }
+
+    buf.mark();
+    buf.flip();
+    assert!(buf.reset_to_mark().is_err());
+
+    return Ok(());
+}
+
+#[test]
+fn test_endian_read_write() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(32);
+    buf.write_u32_le(0x01020304)?;
+    buf.write_u16_be(0x0506)?;
+    buf.write_i64_le(-2)?;
+    buf.write_f32_be(1.5)?;
+    buf.write_i8(-1)?;
+    assert_eq!(buf.position(), 19);
+    assert_eq!(&buf[0..6], &[4, 3, 2, 1, 5, 6]);
+
+    buf.set_position(0);
+    assert_eq!(buf.read_u32_be()?, 0x04030201);
+    assert_eq!(buf.read_u16_le()?, 0x0605);
+    assert_eq!(buf.read_i64_le()?, -2);
+    assert_eq!(buf.read_f32_be()?, 1.5);
+    assert_eq!(buf.read_u8()?, 0xFF);
+    assert_eq!(buf.position(), 19);
+
+    buf.set_position(30);
+    assert_eq!(buf.read_u32_le().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buf.write_u64_be(1).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buf.position(), 30);
+
+    return Ok(());
+}
+
+#[test]
+fn test_peek() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.write_all(&[1, 2, 3, 4, 5, 6, 7, 8])?;
+    buf.set_position(2);
+
+    assert_eq!(buf.peek_u8()?, 3);
+    assert_eq!(buf.peek_u8()?, 3);
+    assert_eq!(buf.peek_slice(3)?, &[3, 4, 5]);
+    assert_eq!(buf.peek_slice(3)?, &[3, 4, 5]);
+    assert_eq!(unsafe { buf.peek::<u32>()? }, u32::from_ne_bytes([3, 4, 5, 6]));
+    assert_eq!(buf.position(), 2);
+    assert_eq!(buf.read_u8()?, 3);
+
+    assert_eq!(buf.peek_slice(6).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(unsafe { buf.peek::<u64>() }.unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    buf.set_position(8);
+    assert_eq!(buf.peek_u8().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buf.peek_slice(0)?, &[]);
+    assert_eq!(buf.position(), 8);
+
+    return Ok(());
+}
+
+#[test]
+fn test_truncate_clear() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(10);
+    buf.set_position(8);
+    buf.truncate(12);
+    assert_eq!(buf.limit(), 10);
+    assert_eq!(buf.position(), 8);
+    buf.truncate(6);
+    assert_eq!(buf.limit(), 6);
+    assert_eq!(buf.position(), 6);
+    buf.truncate(100);
+    assert_eq!(buf.limit(), 6);
+
+    buf.clear();
+    assert_eq!(buf.limit(), 0);
+    assert_eq!(buf.position(), 0);
+    assert_eq!(buf.capacity(), 16);
+
+    buf.set_limit(16);
+    buf.write_all(&[1; 16])?;
+    assert_eq!(buf.position(), 16);
+
+    return Ok(());
+}
+
+#[test]
+fn test_vectored() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(8);
+    let slices = [IoSlice::new(&[1, 2, 3]), IoSlice::new(&[]), IoSlice::new(&[4, 5]), IoSlice::new(&[6])];
+    assert_eq!(buf.write_vectored(&slices)?, 6);
+    assert_eq!(buf.position(), 6);
+    assert_eq!(&buf[0..6], &[1, 2, 3, 4, 5, 6]);
+
+    let slices = [IoSlice::new(&[7]), IoSlice::new(&[8, 9, 10]), IoSlice::new(&[11])];
+    assert_eq!(buf.write_vectored(&slices)?, 2);
+    assert_eq!(buf.position(), 8);
+    assert_eq!(buf.write_vectored(&slices)?, 0);
+
+    buf.flip();
+    let mut a = [0u8; 2];
+    let mut b = [0u8; 3];
+    let mut c = [0u8; 4];
+    let mut slices = [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b), IoSliceMut::new(&mut c)];
+    assert_eq!(buf.read_vectored(&mut slices)?, 8);
+    assert_eq!(buf.position(), 8);
+    assert_eq!(a, [1, 2]);
+    assert_eq!(b, [3, 4, 5]);
+    assert_eq!(c, [6, 7, 8, 0]);
+
+    return Ok(());
+}
+
+#[test]
+fn test_io_slice() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(10);
+    buf.write_all(&[1, 2, 3])?;
+
+    assert_eq!(buf.as_io_slice().len(), buf.remaining());
+    assert_eq!(buf.as_io_slice_mut().len(), 7);
+
+    let mut src = HBuf::allocate_zeroed(4);
+    src.write_all(&[9, 9])?;
+    assert_eq!(&*src.as_io_slice(), &[0, 0]);
+    src.as_io_slice_mut().copy_from_slice(&[7, 8]);
+
+    src.set_position(0);
+    let mut dst = Vec::new();
+    assert_eq!(dst.write_vectored(&[buf.as_io_slice(), src.as_io_slice()])?, 11);
+    assert_eq!(dst.len(), 11);
+    assert_eq!(&dst[7..], &[9, 9, 7, 8]);
+
+    return Ok(());
+}
+
+#[test]
+fn test_buf_read() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(64);
+    buf.write_all(b"first\nsecond\n\nlast")?;
+    buf.flip();
+
+    let mut line = Vec::new();
+    assert_eq!(buf.read_until(b'\n', &mut line)?, 6);
+    assert_eq!(line, b"first\n");
+    line.clear();
+    assert_eq!(buf.read_until(b'\n', &mut line)?, 7);
+    assert_eq!(line, b"second\n");
+    line.clear();
+    assert_eq!(buf.read_until(b'\n', &mut line)?, 1);
+    line.clear();
+    assert_eq!(buf.read_until(b'\n', &mut line)?, 4);
+    assert_eq!(line, b"last");
+    assert_eq!(buf.position(), buf.limit());
+    assert_eq!(buf.fill_buf()?, &[]);
+
+    buf.set_position(0);
+    let lines: Vec<String> = buf.clone().lines().collect::<std::io::Result<_>>()?;
+    assert_eq!(lines, vec!["first", "second", "", "last"]);
+
+    buf.consume(100);
+    assert_eq!(buf.position(), buf.limit());
+
+    return Ok(());
+}
+
+#[test]
+fn test_transaction() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.write_u32_le(1)?;
+    buf.write_u32_le(2)?;
+    buf.flip();
+    buf.set_position(0);
+
+    let err = buf.transaction(|buf| {
+        assert_eq!(buf.read_u32_le()?, 1);
+        assert_eq!(buf.read_u32_le()?, 2);
+        buf.read_u32_le()
+    });
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buf.position(), 0);
+
+    let value = buf.transaction(|buf| buf.read_u32_le())?;
+    assert_eq!(value, 1);
+    assert_eq!(buf.position(), 4);
+
+    let err = buf.transaction(|buf| {
+        buf.read_u32_le()?;
+        buf.set_limit(2);
+        Err::<(), _>(std::io::Error::new(ErrorKind::InvalidData, "bad"))
+    });
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(buf.position(), 2);
+
+    return Ok(());
+}
+
+#[test]
+fn test_read_write_exact_at() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_limit(8);
+    buf.set_position(3);
+
+    buf.write_all_at(4, &[1, 2, 3, 4])?;
+    let mut dst = [0u8; 4];
+    buf.read_exact_at(4, &mut dst)?;
+    assert_eq!(dst, [1, 2, 3, 4]);
+    buf.read_exact_at(8, &mut [])?;
+
+    assert_eq!(buf.write_all_at(5, &[9, 9, 9, 9]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buf.read_exact_at(5, &mut dst).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buf.read_exact_at(usize::MAX, &mut dst).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(buf.write_all_at(9, &[]).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    assert_eq!(&buf[4..8], &[1, 2, 3, 4]);
+    assert_eq!(buf.position(), 3);
+
+    return Ok(());
+}
+
+#[test]
+fn test_growing_writer() -> std::io::Result<()> {
+    let data: Vec<u8> = (0..10240usize).map(|i| (i * 31) as u8).collect();
+    let mut buf = HBuf::allocate_zeroed(16);
+    buf.set_position(4);
+
+    let copied = std::io::copy(&mut data.as_slice(), &mut GrowingWriter(&mut buf))?;
+    assert_eq!(copied, 10240);
+    assert_eq!(buf.position(), 10244);
+    assert_eq!(buf.limit(), 10244);
+    assert!(buf.capacity() >= 10244);
+    assert_eq!(&buf[4..], data.as_slice());
+
+    buf.set_position(0);
+    buf.growing_writer().write_all(&[1, 2, 3])?;
+    assert_eq!(buf.limit(), 10244);
+    assert_eq!(&buf[..5], &[1, 2, 3, 0, 0]);
+
+    let shared = buf.clone();
+    buf.set_position(buf.limit());
+    assert!(buf.growing_writer().write_all(&[1]).is_err());
+    drop(shared);
+
+    let mut buf = HBuf::default();
+    let copied = std::io::copy(&mut data.as_slice(), &mut buf.growing_writer())?;
+    assert_eq!(copied, 10240);
+    assert_eq!(buf.position(), 10240);
+    assert_eq!(buf.as_slice(), data.as_slice());
+
+    return Ok(());
+}
+
+#[test]
+fn test_take() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(10);
+    buf.copy_from_slice(0, &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    buf.set_position(2);
+
+    let mut taken = Vec::new();
+    let mut take = buf.take_bytes(4);
+    assert_eq!(take.remaining(), 4);
+    take.read_to_end(&mut taken)?;
+    assert_eq!(take.remaining(), 0);
+    assert_eq!(taken, vec![2, 3, 4, 5]);
+    assert_eq!(buf.position(), 6);
+
+    let mut rest = [0u8; 8];
+    assert_eq!(buf.take_bytes(100).read(&mut rest)?, 4);
+    assert_eq!(&rest[..4], &[6, 7, 8, 9]);
+    assert_eq!(buf.take_bytes(100).read(&mut rest)?, 0);
+
+    return Ok(());
+}
+
+#[test]
+fn test_chain() -> std::io::Result<()> {
+    let mut header = HBuf::allocate_zeroed(8);
+    header.copy_from_slice(0, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    let mut body = HBuf::allocate_zeroed(8);
+    body.copy_from_slice(0, &[9, 10, 11, 12, 13, 14, 15, 16]);
+
+    let mut chain = header.chain(body);
+    assert_eq!(chain.remaining(), 16);
+    let mut first = [0u8; 3];
+    chain.read_exact(&mut first)?;
+    assert_eq!(chain.remaining(), 13);
+
+    let mut rest = Vec::new();
+    chain.read_to_end(&mut rest)?;
+    assert_eq!(first, [1, 2, 3]);
+    assert_eq!(rest, (4..=16).collect::<Vec<u8>>());
+    assert_eq!(chain.remaining(), 0);
+
+    let (header, body) = chain.into_inner();
+    assert_eq!(header.position(), 8);
+    assert_eq!(body.position(), 8);
+
+    return Ok(());
+}
+
+#[test]
+fn test_ordered() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(16).with_order(ByteOrder::Big);
+    assert_eq!(buf.order(), ByteOrder::Big);
+    buf.write_u32(0x01020304)?;
+    buf.write_i16(-2)?;
+    assert_eq!(buf.position(), 6);
+    assert_eq!(&buf[..6], &[1, 2, 3, 4, 0xFF, 0xFE]);
+
+    buf.set_position(0);
+    assert_eq!(buf.read_u32()?, 0x01020304);
+    assert_eq!(buf.read_i16()?, -2);
+    assert_eq!(buf.get_u16(2), 0x0304);
+
+    buf.set_order(ByteOrder::Little);
+    buf.set_u32(8, 0x01020304);
+    assert_eq!(&buf[8..12], &[4, 3, 2, 1]);
+    assert_eq!(buf.get_u32(0), 0x04030201);
+
+    buf.set_order(ByteOrder::Native);
+    assert_eq!(buf.get_u32(8), u32::from_ne_bytes([4, 3, 2, 1]));
+
+    let buf = buf.into_inner();
+    assert_eq!(buf.position(), 6);
+
+    return Ok(());
+}
@@ -0,0 +1,106 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use heapbuf::SecureHBuf;
+
+static TRACKED: AtomicUsize = AtomicUsize::new(0);
+static TRACKED_ZEROED: AtomicBool = AtomicBool::new(false);
+//Tests that track a deallocation or the amount of locked memory must not run concurrently.
+static TRACKING: Mutex<()> = Mutex::new(());
+
+///
+/// Checks whether the memory at TRACKED is zeroed right before it is deallocated.
+///
+struct CheckingAllocator;
+
+unsafe impl GlobalAlloc for CheckingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if TRACKED.compare_exchange(ptr as usize, 0, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
+            let zeroed = std::slice::from_raw_parts(ptr, layout.size()).iter().all(|b| *b == 0);
+            TRACKED_ZEROED.store(zeroed, Ordering::SeqCst);
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CheckingAllocator = CheckingAllocator;
+
+///
+/// Returns the amount of memory in kB that is locked by this process.
+///
+#[cfg(all(feature = "mlock_support", target_os = "linux"))]
+fn locked_kb() -> usize {
+    let status = std::fs::read_to_string("/proc/self/status").unwrap();
+    let line = status.lines().find_map(|line| line.strip_prefix("VmLck:")).unwrap();
+    line.trim().trim_end_matches("kB").trim().parse().unwrap()
+}
+
+#[test]
+fn test_secure_clone() {
+    let _guard = TRACKING.lock().unwrap();
+    let mut original = SecureHBuf::allocate(32);
+    original.fill(0x55);
+    original.set_limit(16);
+    original.set_position(4);
+
+    let clone = original.clone();
+    assert_ne!(clone.as_ptr(), original.as_ptr());
+    assert_eq!(original.ref_count(), 1);
+    assert_eq!(clone.ref_count(), 1);
+    assert_eq!(clone.limit(), 16);
+    assert_eq!(clone.position(), 4);
+    assert_eq!(clone.is_locked(), original.is_locked());
+
+    TRACKED_ZEROED.store(false, Ordering::SeqCst);
+    TRACKED.store(original.as_ptr() as usize, Ordering::SeqCst);
+    drop(original);
+    assert_eq!(TRACKED.load(Ordering::SeqCst), 0);
+    assert!(TRACKED_ZEROED.load(Ordering::SeqCst));
+
+    assert_eq!(clone.as_slice(), &[0x55u8; 16]);
+    let mut clone = clone;
+    clone.set_limit(32);
+    assert_eq!(clone.as_slice(), &[0x55u8; 32]);
+}
+
+#[test]
+fn test_secure_from_slice() {
+    let _guard = TRACKING.lock().unwrap();
+    let secure = SecureHBuf::from_slice(b"secret");
+    assert_eq!(secure.as_slice(), b"secret");
+    assert_eq!(secure.capacity(), 6);
+    #[cfg(not(feature = "mlock_support"))]
+    assert!(!secure.is_locked());
+}
+
+#[test]
+fn test_secure_shared_handle() {
+    let _guard = TRACKING.lock().unwrap();
+    #[cfg(all(feature = "mlock_support", target_os = "linux"))]
+    let locked_before = locked_kb();
+
+    let mut secure = SecureHBuf::allocate(16);
+    secure.copy_from_slice(0, &[0xAA; 16]);
+    let sub = secure.split(4, 8);
+    let ptr = secure.as_ptr() as usize;
+    let locked = secure.is_locked();
+    drop(secure);
+    assert_eq!(sub.as_slice(), &[0xAA; 8]);
+    #[cfg(all(feature = "mlock_support", target_os = "linux"))]
+    assert_eq!(locked_kb() > locked_before, locked);
+    #[cfg(not(feature = "mlock_support"))]
+    assert!(!locked);
+
+    TRACKED_ZEROED.store(false, Ordering::SeqCst);
+    TRACKED.store(ptr, Ordering::SeqCst);
+    drop(sub);
+    assert_eq!(TRACKED.load(Ordering::SeqCst), 0);
+    assert!(TRACKED_ZEROED.load(Ordering::SeqCst));
+    #[cfg(all(feature = "mlock_support", target_os = "linux"))]
+    assert_eq!(locked_kb(), locked_before);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "serde_support")]
+
+use heapbuf::HBuf;
+
+fn test_data() -> HBuf {
+    let mut buf = HBuf::allocate_zeroed(32);
+    for x in 0..32usize {
+        buf[x] = (x * 7) as u8;
+    }
+    buf.set_limit(20);
+    buf.set_position(5);
+    buf
+}
+
+#[test]
+fn test_bincode() {
+    let buf = test_data();
+    let data = bincode::serialize(&buf).unwrap();
+    let copy: HBuf = bincode::deserialize(data.as_slice()).unwrap();
+    assert_eq!(copy.as_slice(), buf.as_slice());
+    assert_eq!(copy.limit(), 20);
+    assert_eq!(copy.capacity(), 20);
+    assert_eq!(copy.position(), 0);
+    assert_ne!(copy.as_ptr(), buf.as_ptr());
+}
+
+#[test]
+fn test_json() {
+    let buf = test_data();
+    let data = serde_json::to_string(&buf).unwrap();
+    let copy: HBuf = serde_json::from_str(data.as_str()).unwrap();
+    assert_eq!(copy.as_slice(), buf.as_slice());
+    assert_eq!(copy.limit(), 20);
+    assert_eq!(copy.position(), 0);
+}
+
+#[test]
+fn test_empty() {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.set_limit(0);
+    let data = serde_json::to_string(&buf).unwrap();
+    let copy: HBuf = serde_json::from_str(data.as_str()).unwrap();
+    assert_eq!(copy.capacity(), 0);
+
+    let data = bincode::serialize(&buf).unwrap();
+    let copy: HBuf = bincode::deserialize(data.as_slice()).unwrap();
+    assert!(copy.as_slice().is_empty());
+}
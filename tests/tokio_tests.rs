@@ -0,0 +1,36 @@
+#![cfg(feature = "tokio_support")]
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use heapbuf::HBuf;
+
+#[tokio::test]
+async fn test_copy_into() -> std::io::Result<()> {
+    let data: Vec<u8> = (0..200u8).collect();
+    let mut buf = HBuf::allocate_zeroed(256);
+    let copied = tokio::io::copy(&mut data.as_slice(), &mut buf).await?;
+    assert_eq!(copied, 200);
+    assert_eq!(buf.position(), 200);
+    buf.shutdown().await?;
+    buf.flip();
+    assert_eq!(buf.as_slice(), data.as_slice());
+
+    return Ok(());
+}
+
+#[tokio::test]
+async fn test_read_write() -> std::io::Result<()> {
+    let mut buf = HBuf::allocate_zeroed(8);
+    buf.write_u32(0x01020304).await?;
+    assert_eq!(buf.write(&[5, 6, 7, 8, 9]).await?, 4);
+    assert_eq!(buf.write(&[9]).await?, 0);
+    buf.flip();
+
+    assert_eq!(buf.read_u32().await?, 0x01020304);
+    let mut rest = Vec::new();
+    assert_eq!(buf.read_to_end(&mut rest).await?, 4);
+    assert_eq!(rest, vec![5, 6, 7, 8]);
+    assert_eq!(buf.position(), 8);
+
+    return Ok(());
+}
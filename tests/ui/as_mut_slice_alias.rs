@@ -0,0 +1,8 @@
+use heapbuf::HBuf;
+
+fn main() {
+    let mut buf = HBuf::allocate_zeroed(16);
+    let a = buf.as_mut_slice();
+    let b = buf.as_mut_slice();
+    a[0] = b[0];
+}
@@ -0,0 +1,6 @@
+use heapbuf::HBuf;
+
+fn main() {
+    let frozen = HBuf::allocate_zeroed(16).freeze();
+    frozen[0] = 1;
+}